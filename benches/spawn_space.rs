@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gamejam_concoction::{
+	assets::{Spawnable, SpawnableArchetype, SpawnableIngredient},
+	game::{
+		biome::BiomeMap,
+		ingredient::SpeciesRegistry,
+		world::{plan_spawnables, LinearSpawnSpace, OccupiedSpawnSpace, SPAWN_ATTEMPTS},
+	},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Same sizes and weights as the spawnables loaded from the models
+fn spawnables() -> Vec<Spawnable> {
+	use SpawnableArchetype::*;
+
+	let spawnable = |id, archetype, spawn_weight, size| Spawnable {
+		id,
+		archetype,
+		scene: Handle::default(),
+		// Only the berry bush of the bushes
+		ingredient: (archetype != Tree && (archetype != Bush || id == 2))
+			.then(SpawnableIngredient::default),
+		spawn_weight,
+		size,
+		collider: None,
+	};
+
+	vec![
+		spawnable(0, Tree, 1.2, 2.8),
+		spawnable(0, Bush, 1.0 / 3.0, 1.5),
+		spawnable(1, Bush, 1.0 / 3.0, 2.0),
+		spawnable(2, Bush, 0.3 / 3.0, 2.0),
+		spawnable(0, Mushroom, 0.3 / 2.0, 0.6),
+		spawnable(1, Mushroom, 0.3 / 2.0, 0.6),
+		spawnable(0, Root, 0.25, 0.6),
+		spawnable(0, Plant, 0.25, 0.7),
+	]
+}
+
+fn plan_world(c: &mut Criterion) {
+	let spawnables = spawnables();
+	let spawnables = spawnables.iter().collect::<Vec<_>>();
+	let species_registry = SpeciesRegistry::default();
+	let biome_map = BiomeMap::new(&mut StdRng::seed_from_u64(22));
+
+	let mut group = c.benchmark_group("plan_spawnables");
+	group.sample_size(10);
+
+	for attempts in [SPAWN_ATTEMPTS, 50_000] {
+		group.bench_with_input(
+			BenchmarkId::new("grid", attempts),
+			&attempts,
+			|b, &attempts| {
+				b.iter(|| {
+					plan_spawnables(
+						&spawnables,
+						&species_registry,
+						&biome_map,
+						&mut OccupiedSpawnSpace::default(),
+						attempts,
+						&mut StdRng::seed_from_u64(22),
+					)
+				})
+			},
+		);
+		group.bench_with_input(
+			BenchmarkId::new("linear", attempts),
+			&attempts,
+			|b, &attempts| {
+				b.iter(|| {
+					plan_spawnables(
+						&spawnables,
+						&species_registry,
+						&biome_map,
+						&mut LinearSpawnSpace::default(),
+						attempts,
+						&mut StdRng::seed_from_u64(22),
+					)
+				})
+			},
+		);
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, plan_world);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{game::ingredient::Grind, prelude::*};
 use bevy::ecs::query::ReadOnlyWorldQuery;
 use bevy::{
 	gltf::Gltf,
@@ -27,6 +27,85 @@ impl Plugin for AssetLoadingPlugin {
 	}
 }
 
+/// Scenes and colliders of ground up ingredients, one for every level past `Grind::Whole`
+#[derive(Resource)]
+pub struct GrindAssets {
+	levels: [(Handle<Scene>, Collider); 3],
+}
+
+impl GrindAssets {
+	pub fn get(&self, grind: Grind) -> Option<&(Handle<Scene>, Collider)> {
+		match grind {
+			Grind::Whole => None,
+			Grind::Coarse => Some(&self.levels[0]),
+			Grind::Fine => Some(&self.levels[1]),
+			Grind::Paste => Some(&self.levels[2]),
+		}
+	}
+}
+
+/// Builds a scene with a single mesh, named so `NamedMaterials` can color it
+fn mesh_scene(
+	name: &'static str,
+	mesh: Handle<Mesh>,
+	material: Handle<StandardMaterial>,
+	transform: Transform,
+) -> Scene {
+	parts_scene(vec![(name, mesh, material, transform)])
+}
+
+/// Named mesh in a scene built by `parts_scene`
+type ScenePart = (
+	&'static str,
+	Handle<Mesh>,
+	Handle<StandardMaterial>,
+	Transform,
+);
+
+/// Builds a scene out of several named meshes
+fn parts_scene(parts: Vec<ScenePart>) -> Scene {
+	let mut world = World::new();
+	for (name, mesh, material, transform) in parts {
+		world.spawn((
+			Name::new(name),
+			PbrBundle {
+				mesh,
+				material,
+				transform,
+				..default()
+			},
+		));
+	}
+
+	Scene::new(world)
+}
+
+/// Leaves sticking out of the ground in a circle
+fn leaf_parts(
+	mesh: &Handle<Mesh>,
+	material: &Handle<StandardMaterial>,
+	count: usize,
+	height: f32,
+	tilt: f32,
+	scale: f32,
+) -> Vec<ScenePart> {
+	(0..count)
+		.map(|i| {
+			let rotation = Quat::from_rotation_y(i as f32 / count as f32 * PI * 2.0)
+				* Quat::from_rotation_x(tilt);
+
+			(
+				"Leaves",
+				mesh.clone(),
+				material.clone(),
+				Transform::from_translation(Vec3::Y * height + rotation * Vec3::Y * 0.18 * scale)
+					.with_rotation(rotation)
+					.with_scale(Vec3::splat(scale)),
+			)
+		})
+		.collect()
+}
+
 #[derive(Resource)]
 pub struct CalculatedColliders {
 	pub cauldron_collider: Collider,
@@ -45,7 +124,7 @@ fn setup(
 	mut material_assets: ResMut<Assets<StandardMaterial>>,
 	mut foliage_assets: ResMut<Assets<FoliageMaterial>>,
 	game_assets: Res<GameAssets>,
-	scene_assets: Res<Assets<Scene>>,
+	mut scene_assets: ResMut<Assets<Scene>>,
 	gltfs: Res<Assets<Gltf>>,
 ) {
 	DEFAULT_FOLIAGE
@@ -90,6 +169,7 @@ fn setup(
 			scene: scene.clone(),
 			ingredient: match i {
 				2 => Some(SpawnableIngredient {
+					dig: false,
 					pick_event: PickUpEvent::RemoveNamedChild("Berry"),
 					inventory_scene: game_assets.berry_scene.clone(),
 					collider: Collider::compound(vec![
@@ -127,6 +207,7 @@ fn setup(
 			archetype: SpawnableArchetype::Mushroom,
 			scene: scene.clone(),
 			ingredient: Some(SpawnableIngredient {
+				dig: false,
 				pick_event: PickUpEvent::Destroy,
 				inventory_scene: scene.clone(),
 				collider: match i {
@@ -170,6 +251,179 @@ fn setup(
 		spawnable_assets.add(spawnable);
 	}
 
+	// No models for roots and plants, so they are put together from simple shapes
+	let part_material = material_assets.add(StandardMaterial::default());
+	let leaf_mesh = mesh_assets.add(Mesh::from(shape::Capsule {
+		radius: 0.03,
+		depth: 0.3,
+		..default()
+	}));
+	let bulb_mesh = mesh_assets.add(Mesh::from(shape::UVSphere {
+		radius: 0.15,
+		..default()
+	}));
+	let stem_mesh = mesh_assets.add(Mesh::from(shape::Capsule {
+		radius: 0.025,
+		depth: 0.6,
+		..default()
+	}));
+
+	let mut root_parts = leaf_parts(&leaf_mesh, &part_material, 4, 0.05, 0.35, 1.0);
+	root_parts.push((
+		"Root",
+		bulb_mesh.clone(),
+		part_material.clone(),
+		Transform::from_xyz(0.0, 0.02, 0.0).with_scale(Vec3::new(1.0, 0.6, 1.0)),
+	));
+
+	let mut root_item_parts = leaf_parts(&leaf_mesh, &part_material, 3, 0.38, 0.3, 0.6);
+	root_item_parts.push((
+		"Root",
+		mesh_assets.add(Mesh::from(shape::Capsule {
+			radius: 0.08,
+			depth: 0.25,
+			..default()
+		})),
+		part_material.clone(),
+		Transform::from_xyz(0.0, 0.205, 0.0),
+	));
+
+	// What is left after a root is dug out
+	let hole_scene = parts_scene(vec![
+		(
+			"Hole",
+			mesh_assets.add(Mesh::from(shape::Cylinder {
+				radius: 0.22,
+				height: 0.02,
+				resolution: 16,
+				segments: 1,
+			})),
+			part_material.clone(),
+			Transform::from_xyz(0.0, 0.01, 0.0),
+		),
+		(
+			"Dirt",
+			bulb_mesh.clone(),
+			part_material.clone(),
+			Transform::from_xyz(0.3, 0.0, 0.0).with_scale(Vec3::new(0.8, 0.35, 0.8)),
+		),
+	]);
+
+	spawnable_assets.add(Spawnable {
+		id: 0,
+		archetype: SpawnableArchetype::Root,
+		scene: scene_assets.add(parts_scene(root_parts)),
+		ingredient: Some(SpawnableIngredient {
+			dig: true,
+			pick_event: PickUpEvent::Replace(scene_assets.add(hole_scene)),
+			inventory_scene: scene_assets.add(parts_scene(root_item_parts)),
+			collider: Collider::capsule(Vec3::Y * 0.08, Vec3::Y * 0.33, 0.08),
+		}),
+		spawn_weight: 0.25,
+		size: 0.6,
+		collider: None,
+	});
+
+	let mut plant_parts = leaf_parts(&leaf_mesh, &part_material, 3, 0.05, 0.9, 1.0);
+	plant_parts.extend([
+		(
+			"Stem",
+			stem_mesh.clone(),
+			part_material.clone(),
+			Transform::from_xyz(0.0, 0.33, 0.0),
+		),
+		(
+			"Flower",
+			bulb_mesh.clone(),
+			part_material.clone(),
+			Transform::from_xyz(0.0, 0.7, 0.0).with_scale(Vec3::new(0.7, 0.45, 0.7)),
+		),
+	]);
+
+	let plant_item_parts = vec![
+		(
+			"Stem",
+			stem_mesh,
+			part_material.clone(),
+			Transform::from_xyz(0.0, 0.15, 0.0).with_scale(Vec3::new(1.0, 0.4, 1.0)),
+		),
+		(
+			"Flower",
+			bulb_mesh,
+			part_material,
+			Transform::from_xyz(0.0, 0.32, 0.0).with_scale(Vec3::new(0.7, 0.45, 0.7)),
+		),
+	];
+
+	spawnable_assets.add(Spawnable {
+		id: 0,
+		archetype: SpawnableArchetype::Plant,
+		scene: scene_assets.add(parts_scene(plant_parts)),
+		ingredient: Some(SpawnableIngredient {
+			dig: false,
+			pick_event: PickUpEvent::RemoveNamedChild("Flower"),
+			inventory_scene: scene_assets.add(parts_scene(plant_item_parts)),
+			collider: Collider::capsule(Vec3::Y * 0.05, Vec3::Y * 0.3, 0.08),
+		}),
+		spawn_weight: 0.25,
+		size: 0.7,
+		collider: None,
+	});
+
+	// Crushed ingredient comes from the model, finer grinds are simple enough to build here
+	let mashed_material = material_assets.add(StandardMaterial::default());
+
+	let fine_scene = mesh_scene(
+		"Mashed",
+		mesh_assets.add(Mesh::from(shape::UVSphere {
+			radius: 0.2,
+			..default()
+		})),
+		mashed_material.clone(),
+		Transform::from_xyz(0.0, 0.08, 0.0).with_scale(Vec3::new(1.0, 0.45, 1.0)),
+	);
+
+	let paste_scene = mesh_scene(
+		"Mashed",
+		mesh_assets.add(Mesh::from(shape::Cylinder {
+			radius: 0.25,
+			height: 0.05,
+			resolution: 16,
+			segments: 1,
+		})),
+		mashed_material,
+		Transform::from_xyz(0.0, 0.025, 0.0),
+	);
+
+	commands.insert_resource(GrindAssets {
+		levels: [
+			(
+				game_assets.crushed_ingredient_scene.clone(),
+				Collider::compound(vec![(
+					Vec3::new(0.0, 0.15, 0.0),
+					Quat::IDENTITY,
+					Collider::round_cone(0.132, 0.225, 0.05),
+				)]),
+			),
+			(
+				scene_assets.add(fine_scene),
+				Collider::compound(vec![(
+					Vec3::new(0.0, 0.08, 0.0),
+					Quat::IDENTITY,
+					Collider::round_cylinder(0.03, 0.17, 0.05),
+				)]),
+			),
+			(
+				scene_assets.add(paste_scene),
+				Collider::compound(vec![(
+					Vec3::new(0.0, 0.025, 0.0),
+					Quat::IDENTITY,
+					Collider::round_cylinder(0.005, 0.23, 0.02),
+				)]),
+			),
+		],
+	});
+
 	// YES I DO IT AT RUNTIME, NO TIME TO FIX BUCK OFF
 	// SERDE DOES NOT WANT TO COOPERATE, SO YOU'LL HAVE TO WAIT 10 SECONDS OF LOADING SCREEN
 	commands.insert_resource(CalculatedColliders {
@@ -197,6 +451,8 @@ fn setup(
 
 #[derive(Clone, Debug, Default)]
 pub struct SpawnableIngredient {
+	/// Can't just be picked up, has to be dug out
+	pub dig: bool,
 	pub pick_event: PickUpEvent,
 	pub inventory_scene: Handle<Scene>,
 	pub collider: Collider,
@@ -244,7 +500,6 @@ pub fn compute_collider(
 	Collider::compound(shapes)
 }
 
-#[allow(dead_code)]
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub enum PickUpEvent {
 	#[default]
@@ -287,6 +542,8 @@ pub enum SpawnableArchetype {
 	Tree,
 	Bush,
 	Mushroom,
+	Root,
+	Plant,
 }
 
 #[derive(AssetCollection, Resource)]
@@ -367,6 +624,14 @@ pub struct GameAssets {
 	pub delishs_sound: Handle<AudioSource>,
 	#[asset(path = "sounds/filling_potion.ogg")]
 	pub filling_potion_sound: Handle<AudioSource>,
+	#[asset(path = "sounds/its_ready.ogg")]
+	pub its_ready_sound: Handle<AudioSource>,
+	#[asset(path = "sounds/oh_no.ogg")]
+	pub oh_no_sound: Handle<AudioSource>,
+	#[asset(path = "sounds/grind_done.ogg")]
+	pub grind_done_sound: Handle<AudioSource>,
+	#[asset(path = "sounds/ting.ogg")]
+	pub ting_sound: Handle<AudioSource>,
 	#[asset(
 		paths(
 			"sounds/grind_1.ogg",
@@ -3,17 +3,21 @@ use bevy::{
 	math::{Vec3Swizzles, Vec4Swizzles},
 	render::view::RenderLayers,
 };
+use bevy_inspector_egui::egui::lerp;
 
 use crate::{
-	assets::{CalculatedColliders, SceneInstanceReady},
+	assets::{CalculatedColliders, GrindAssets, SceneInstanceReady},
 	prelude::*,
 };
 
 use super::{
 	backpack::InventoryCamera,
-	effects::{generate_effects_from_qp, generate_qp_from_ingredients, ActiveEffects},
+	effects::{ideal_simmer_time, ActiveEffects, EffectRegistry, SIMMER_TOLERANCE},
 	ingredient::{Grind, Ingredient},
-	items::{DroppedItem, Grabber, Item, ItemSize, Potion},
+	items::{DroppedItem, Grabber, InventoryItemBundle, Item, ItemSize, Potion},
+	journal::{JournalEntry, RecipeJournal},
+	vitals::{DamageEvent, DamageSource},
+	world::WorldSeed,
 };
 
 pub struct AlchemyPlugin;
@@ -38,21 +42,25 @@ impl Plugin for AlchemyPlugin {
 				consume_eaten.after(check_eaten),
 				consume_cauldroned.after(check_cauldroned),
 				mash_ingredient,
+				heat_cauldrons,
+				scoop_with_ladle,
 				change_color,
 				spawn_new_bottle,
 			)
 				.in_set(OnUpdate(GameState::InGame)),
 		)
 		.register_type::<Mortar>()
-		.register_type::<Cauldron>();
+		.register_type::<Cauldron>()
+		.register_type::<Simmer>();
 	}
 }
 
 #[derive(Component, Debug, Clone, Copy, Reflect, FromReflect)]
 pub struct Eaten;
 
+/// Item is being sucked into the cauldron entity
 #[derive(Component, Debug, Clone, Copy, Reflect, FromReflect)]
-pub struct Cauldroned;
+pub struct Cauldroned(pub Entity);
 
 #[derive(Component)]
 pub struct AlchemyTable;
@@ -63,8 +71,107 @@ pub struct Mortar(bool);
 #[derive(Component)]
 pub struct Pestle;
 
+/// How much of the brew goes into one bottle. Every ingredient adds 1.0 to the volume.
+pub const BOTTLE_VOLUME: f32 = 2.0;
+
+/// What an ingredient looked like in the backpack, so the ladle can give it back
+#[derive(Clone)]
+pub struct ScoopableIngredient {
+	pub scene: Handle<Scene>,
+	pub materials: NamedMaterials,
+	pub collider: Collider,
+	pub size: f32,
+}
+
 #[derive(Component, Default, Reflect)]
-pub struct Cauldron(SmallVec<[Ingredient; 6]>);
+pub struct Cauldron {
+	ingredients: SmallVec<[Ingredient; 6]>,
+	/// Same order as `ingredients`
+	#[reflect(ignore)]
+	scoopables: SmallVec<[ScoopableIngredient; 6]>,
+	/// Brew left in the cauldron
+	volume: f32,
+}
+
+impl Cauldron {
+	/// Average color of all ingredients inside
+	pub fn liquid_color(&self) -> Vec3 {
+		if self.ingredients.is_empty() {
+			Vec3::new(0.6, 0.8, 0.97)
+		} else {
+			self.ingredients
+				.iter()
+				.map(|i| Vec4::from(i.color.as_rgba_f32()).xyz())
+				.sum::<Vec3>()
+				/ self.ingredients.len() as f32
+		}
+	}
+
+	/// How many bottles can still be filled
+	pub fn doses(&self) -> usize {
+		(self.volume / BOTTLE_VOLUME).ceil() as usize
+	}
+
+	pub fn add_ingredient(&mut self, ingredient: Ingredient, scoopable: ScoopableIngredient) {
+		self.ingredients.push(ingredient);
+		self.scoopables.push(scoopable);
+		self.volume += 1.0;
+	}
+
+	/// Takes the last added ingredient back out
+	pub fn scoop(&mut self) -> Option<(Ingredient, ScoopableIngredient)> {
+		let ingredient = self.ingredients.pop()?;
+		let scoopable = self.scoopables.pop()?;
+
+		self.volume = (self.volume - 1.0).max(0.0);
+		if self.volume <= 0.0 {
+			self.ingredients.clear();
+			self.scoopables.clear();
+		}
+
+		Some((ingredient, scoopable))
+	}
+
+	/// Takes one bottle worth of the brew.
+	/// Returns brew ingredients and how full the bottle is (0.0 to 1.0)
+	pub fn take_dose(&mut self) -> (Vec<Ingredient>, f32) {
+		let volume = self.volume.min(BOTTLE_VOLUME);
+		let ingredients = self.ingredients.to_vec();
+
+		self.volume -= volume;
+
+		// Last bottle takes everything that's left
+		if self.volume <= 0.0 {
+			self.volume = 0.0;
+			self.ingredients.clear();
+			self.scoopables.clear();
+		}
+
+		(ingredients, volume / BOTTLE_VOLUME)
+	}
+}
+
+/// Scoops the last ingredient out of the cauldron it's dipped into
+#[derive(Component, Default)]
+pub struct Ladle {
+	/// Only one scoop per dip
+	in_cauldron: bool,
+}
+
+/// Where the bowl of the ladle is, relative to the ladle
+const LADLE_BOWL_OFFSET: Vec3 = Vec3::new(0.0, -0.45, 0.0);
+
+/// Heat source, cauldrons placed right above it start simmering
+#[derive(Component)]
+pub struct Burner;
+
+/// How hot the cauldron is and for how long its contents were simmering
+#[derive(Component, Default, Reflect)]
+pub struct Simmer {
+	/// Goes from 0.0 (cold) to 1.0 (boiling)
+	pub heat: f32,
+	pub time: f32,
+}
 
 #[derive(Component)]
 pub struct PlayerHead;
@@ -86,6 +193,8 @@ impl Default for SecondWorldBundle {
 
 fn init_alchemy_table(
 	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut standard_mat: ResMut<Assets<StandardMaterial>>,
 	game_assets: Res<GameAssets>,
 	calculated_colliders: Res<CalculatedColliders>,
 	gltfs: Res<Assets<Gltf>>,
@@ -165,30 +274,131 @@ fn init_alchemy_table(
 		SecondWorldBundle::default(),
 	));
 
-	commands.spawn((
-		Pestle,
-		Name::new("Cauldron"),
-		Cauldron::default(),
-		Item::AlchemyTool,
-		ItemSize::new(2.5, false),
-		RigidBody::Dynamic,
-		Velocity::default(),
-		SceneBundle {
-			scene: game_assets.cauldron_scene.clone(),
-			transform: Transform::from_xyz(7.0, 5.0, 0.0).with_scale(Vec3::splat(2.5)),
-			..default()
-		},
-		Damping {
-			angular_damping: 0.5,
-			linear_damping: 0.5,
-		},
-		ColliderMassProperties::Density(0.8),
-		NamedMaterials(smallvec![NamedMaterial::new("Cauldron", Color::DARK_GRAY)]),
-		LockedAxes::TRANSLATION_LOCKED_Z,
-		calculated_colliders.cauldron_collider.clone(),
-		//Collider::compound(vec![(Vec3::ZERO,Quat::IDENTITY,Collider::cuboid(0.4, 0.4, 0.4))]),
-		SecondWorldBundle::default(),
-	));
+	spawn_cauldron(
+		&mut commands,
+		&game_assets,
+		&calculated_colliders,
+		Vec3::new(7.0, 5.0, 0.0),
+	);
+	spawn_cauldron(
+		&mut commands,
+		&game_assets,
+		&calculated_colliders,
+		Vec3::new(4.0, 7.0, 0.0),
+	);
+
+	// Burner. No model for it, so it's made out of primitives
+	commands
+		.spawn((
+			Burner,
+			Name::new("Burner"),
+			Item::AlchemyTool,
+			ItemSize::new(1.0, false),
+			RigidBody::Dynamic,
+			Velocity::default(),
+			PbrBundle {
+				mesh: meshes.add(Mesh::from(shape::Cylinder {
+					radius: 0.35,
+					height: 0.25,
+					..default()
+				})),
+				material: standard_mat.add(StandardMaterial {
+					base_color: Color::rgb(0.25, 0.25, 0.3),
+					perceptual_roughness: 0.6,
+					..default()
+				}),
+				transform: Transform::from_xyz(2.0, 6.0, 0.0),
+				..default()
+			},
+			Damping {
+				angular_damping: 0.5,
+				linear_damping: 0.5,
+			},
+			ColliderMassProperties::Density(2.0),
+			LockedAxes::TRANSLATION_LOCKED_Z,
+			Collider::cylinder(0.125, 0.35),
+			SecondWorldBundle::default(),
+		))
+		.with_children(|commands| {
+			commands.spawn((
+				Name::new("Flame"),
+				PbrBundle {
+					mesh: meshes.add(Mesh::from(shape::UVSphere {
+						radius: 0.15,
+						..default()
+					})),
+					material: standard_mat.add(StandardMaterial {
+						base_color: Color::ORANGE,
+						unlit: true,
+						..default()
+					}),
+					transform: Transform::from_xyz(0.0, 0.2, 0.0)
+						.with_scale(Vec3::new(1.0, 1.6, 1.0)),
+					..default()
+				},
+				RenderLayers::layer(2),
+			));
+		});
+
+	// Ladle, also made out of primitives
+	let ladle_material = standard_mat.add(StandardMaterial {
+		base_color: Color::rgb(0.55, 0.55, 0.6),
+		metallic: 0.6,
+		perceptual_roughness: 0.4,
+		..default()
+	});
+
+	commands
+		.spawn((
+			Ladle::default(),
+			Name::new("Ladle"),
+			Item::AlchemyTool,
+			ItemSize::new(1.0, false),
+			RigidBody::Dynamic,
+			Velocity::default(),
+			PbrBundle {
+				mesh: meshes.add(Mesh::from(shape::Capsule {
+					radius: 0.04,
+					depth: 0.9,
+					..default()
+				})),
+				material: ladle_material.clone(),
+				transform: Transform::from_xyz(6.0, 6.0, 0.0)
+					.with_rotation(Quat::from_rotation_z(-0.4)),
+				..default()
+			},
+			Damping {
+				angular_damping: 0.5,
+				linear_damping: 0.5,
+			},
+			ColliderMassProperties::Density(1.5),
+			LockedAxes::TRANSLATION_LOCKED_Z,
+			Collider::compound(vec![
+				(
+					Vec3::ZERO,
+					Quat::IDENTITY,
+					Collider::capsule(Vec3::Y * -0.45, Vec3::Y * 0.45, 0.04),
+				),
+				(LADLE_BOWL_OFFSET, Quat::IDENTITY, Collider::ball(0.15)),
+			]),
+			SecondWorldBundle::default(),
+		))
+		.with_children(|commands| {
+			commands.spawn((
+				Name::new("Bowl"),
+				PbrBundle {
+					mesh: meshes.add(Mesh::from(shape::UVSphere {
+						radius: 0.15,
+						..default()
+					})),
+					material: ladle_material,
+					transform: Transform::from_translation(LADLE_BOWL_OFFSET)
+						.with_scale(Vec3::new(1.0, 0.6, 1.0)),
+					..default()
+				},
+				RenderLayers::layer(2),
+			));
+		});
 
 	// Player head
 	commands.spawn((
@@ -216,6 +426,39 @@ fn init_alchemy_table(
 	spawn_empty_bottle(&mut commands, &game_assets, &gltfs, &calculated_colliders);
 }
 
+pub fn spawn_cauldron(
+	commands: &mut Commands,
+	game_assets: &GameAssets,
+	colliders: &CalculatedColliders,
+	translation: Vec3,
+) {
+	commands.spawn((
+		Pestle,
+		Name::new("Cauldron"),
+		Cauldron::default(),
+		Simmer::default(),
+		Item::AlchemyTool,
+		ItemSize::new(2.5, false),
+		RigidBody::Dynamic,
+		Velocity::default(),
+		SceneBundle {
+			scene: game_assets.cauldron_scene.clone(),
+			transform: Transform::from_translation(translation).with_scale(Vec3::splat(2.5)),
+			..default()
+		},
+		Damping {
+			angular_damping: 0.5,
+			linear_damping: 0.5,
+		},
+		ColliderMassProperties::Density(0.8),
+		NamedMaterials(smallvec![NamedMaterial::new("Cauldron", Color::DARK_GRAY)]),
+		LockedAxes::TRANSLATION_LOCKED_Z,
+		colliders.cauldron_collider.clone(),
+		//Collider::compound(vec![(Vec3::ZERO,Quat::IDENTITY,Collider::cuboid(0.4, 0.4, 0.4))]),
+		SecondWorldBundle::default(),
+	));
+}
+
 pub fn spawn_empty_bottle(
 	commands: &mut Commands,
 	game_assets: &GameAssets,
@@ -254,35 +497,36 @@ pub fn spawn_empty_bottle(
 	));
 }
 
-#[derive(Resource, Default)]
+/// Liquid material of the cauldron, each cauldron has its own
+#[derive(Component, Default)]
 struct CauldronLiquidMaterial(Handle<FoliageMaterial>);
 
 fn get_cauldron_liquid_material(
 	mut commands: Commands,
-	cauldron: Query<Entity, (With<Cauldron>, Added<SceneInstanceReady>)>,
+	cauldron_query: Query<Entity, (With<Cauldron>, Added<SceneInstanceReady>)>,
 	children_query: Query<&Children>,
 	mesh_query: Query<&Name, With<Handle<Mesh>>>,
 	mut materials: ResMut<Assets<FoliageMaterial>>,
 ) {
-	let Ok(cauldron) = cauldron.get_single() else {
-		return;
-	};
-
-	for child in children_query.iter_descendants(cauldron) {
-		let Ok(name) = mesh_query.get(child) else { continue; };
-
-		if name.contains("Liquid") {
-			let material = materials.add(FoliageMaterial {
-				color: Color::rgb(0.6, 0.8, 0.97),
-				sss: true,
-			});
-
-			commands
-				.entity(child)
-				.remove::<Handle<StandardMaterial>>()
-				.insert(material.clone());
-
-			commands.insert_resource(CauldronLiquidMaterial(material.clone()))
+	for cauldron in &cauldron_query {
+		for child in children_query.iter_descendants(cauldron) {
+			let Ok(name) = mesh_query.get(child) else { continue; };
+
+			if name.contains("Liquid") {
+				let material = materials.add(FoliageMaterial {
+					color: Color::rgb(0.6, 0.8, 0.97),
+					sss: true,
+				});
+
+				commands
+					.entity(child)
+					.remove::<Handle<StandardMaterial>>()
+					.insert(material.clone());
+
+				commands
+					.entity(cauldron)
+					.insert(CauldronLiquidMaterial(material.clone()));
+			}
 		}
 	}
 }
@@ -295,7 +539,6 @@ fn get_cauldron_liquid_material(
 struct CenterOfMass(Vec3);
 
 /// Center of mass calculation
-
 fn calculate_com(
 	mut commands: Commands,
 	items: Query<(Entity, &Collider), (With<Item>, Or<(Changed<Collider>, Without<CenterOfMass>)>)>,
@@ -377,7 +620,12 @@ fn consume_eaten(
 	time: Res<Time>,
 	game_assets: Res<GameAssets>,
 	sound: Res<Audio>,
+	ingredient_query: Query<&Ingredient>,
 	mut active_effects: ResMut<ActiveEffects>,
+	effect_registry: Res<EffectRegistry>,
+	mut journal: ResMut<RecipeJournal>,
+	world_seed: Res<WorldSeed>,
+	mut damage_events: EventWriter<DamageEvent>,
 ) {
 	if eaten_query.is_empty() {
 		return;
@@ -404,6 +652,17 @@ fn consume_eaten(
 				Item::Ingredient => {
 					commands.entity(entity).despawn_recursive();
 
+					if let Some(ingredient) = ingredient_query
+						.get(entity)
+						.ok()
+						.filter(|i| i.is_poisonous())
+					{
+						damage_events.send(DamageEvent {
+							amount: 20.0 * ingredient.size,
+							source: DamageSource::PoisonousIngredient,
+						});
+					}
+
 					if rng.gen_bool(0.05) {
 						sound.play(game_assets.delishs_sound.clone());
 					} else {
@@ -411,16 +670,19 @@ fn consume_eaten(
 					}
 				}
 				Item::Potion(potion) => {
-					if let Potion::Filled {
-						ingridients,
-						color: _,
-					} = potion
+					if let (Some((quality, purity, effects)), Potion::Filled { ingridients, .. }) =
+						(potion.brew(&effect_registry), potion)
 					{
-						let (quality, purity) = generate_qp_from_ingredients(ingridients);
-						let effects = generate_effects_from_qp(quality, purity);
+						journal.0.push(JournalEntry::new(
+							ingridients,
+							quality,
+							purity,
+							&effects,
+							*world_seed,
+						));
 
 						for effect in effects {
-							active_effects.0.push(effect);
+							active_effects.push(effect, &effect_registry);
 						}
 						sound.play(game_assets.drink_sound.clone());
 					}
@@ -438,65 +700,179 @@ fn consume_eaten(
 	}
 }
 
+/// Keeps enough empty bottles around to bottle the biggest brew
 fn spawn_new_bottle(
 	mut commands: Commands,
 	game_assets: Res<GameAssets>,
 	gltfs: Res<Assets<Gltf>>,
 	calculated_colliders: Res<CalculatedColliders>,
 	potion: Query<&Item, Without<DroppedItem>>,
+	cauldron_query: Query<&Cauldron>,
 ) {
+	const MAX_EMPTY_BOTTLES: usize = 4;
+
+	let needed_bottles = cauldron_query
+		.iter()
+		.map(|c| c.doses())
+		.max()
+		.unwrap_or_default()
+		.clamp(1, MAX_EMPTY_BOTTLES);
+
 	if potion
 		.iter()
 		.filter(|a| matches!(a, Item::Potion(Potion::Empty)))
-		.count() == 0
+		.count() < needed_bottles
 	{
 		spawn_empty_bottle(&mut commands, &game_assets, &gltfs, &calculated_colliders);
 	}
 }
 
 fn change_color(
-	cauldron: Query<&Cauldron>,
-	cauldron_mat: Option<Res<CauldronLiquidMaterial>>,
+	cauldron_query: Query<(&Cauldron, &CauldronLiquidMaterial)>,
 	mut materials: ResMut<Assets<FoliageMaterial>>,
 	time: Res<Time>,
 ) {
-	let Some(Some(material)) = cauldron_mat.map(|res| materials.get_mut(&res.0)) else { return; };
-	let cauldron = cauldron.single();
+	for (cauldron, cauldron_mat) in &cauldron_query {
+		let Some(material) = materials.get_mut(&cauldron_mat.0) else { continue; };
 
-	let color = Vec4::from(material.color.as_rgba_f32()).xyz();
+		let color = Vec4::from(material.color.as_rgba_f32()).xyz();
 
-	let desired_color = if cauldron.0.is_empty() {
-		Vec3::new(0.6, 0.8, 0.97)
-	} else {
-		cauldron
-			.0
-			.iter()
-			.map(|i| Vec4::from(i.color.as_rgba_f32()).xyz())
-			.sum::<Vec3>()
-			/ cauldron.0.len() as f32
-	};
+		let lerped = color.lerp(
+			cauldron.liquid_color(),
+			1.0 - 0.3f32.powf(time.delta_seconds()),
+		);
+
+		material.color = Color::rgb(lerped.x, lerped.y, lerped.z);
+	}
+}
+
+/// Heats up cauldrons standing on a burner and counts simmering time.
+/// Plays a sound when the brew is ready, and when it's overcooked.
+fn heat_cauldrons(
+	mut cauldron_query: Query<(&GlobalTransform, &Cauldron, &mut Simmer)>,
+	burner_query: Query<&GlobalTransform, With<Burner>>,
+	game_assets: Res<GameAssets>,
+	sound: Res<Audio>,
+	time: Res<Time>,
+) {
+	for (cauldron_transform, cauldron, mut simmer) in &mut cauldron_query {
+		let cauldron_pos = cauldron_transform.translation();
+
+		let is_heated = burner_query.iter().any(|burner_transform| {
+			let relative = cauldron_pos - burner_transform.translation();
+			relative.x.abs() < 0.6 && (0.0..1.2).contains(&relative.y)
+		});
+
+		let desired_heat = if is_heated { 1.0 } else { 0.0 };
+		simmer.heat = lerp(
+			simmer.heat..=desired_heat,
+			1.0 - 0.5f32.powf(time.delta_seconds()),
+		);
+
+		if cauldron.ingredients.is_empty() || simmer.heat < 0.5 {
+			continue;
+		}
+
+		let ideal_time = ideal_simmer_time(&cauldron.ingredients);
+		let previous_time = simmer.time;
+		simmer.time += time.delta_seconds() * simmer.heat;
+
+		let ready_time = ideal_time * (1.0 - SIMMER_TOLERANCE);
+		let overcooked_time = ideal_time * (1.0 + SIMMER_TOLERANCE);
+
+		if previous_time < ready_time && simmer.time >= ready_time {
+			sound.play(game_assets.its_ready_sound.clone());
+		} else if previous_time < overcooked_time && simmer.time >= overcooked_time {
+			sound.play(game_assets.oh_no_sound.clone());
+		}
+	}
+}
+
+/// Dipping the ladle into a cauldron takes the last added ingredient back out
+fn scoop_with_ladle(
+	mut commands: Commands,
+	mut ladle_query: Query<(&GlobalTransform, &mut Ladle), (Without<Eaten>, Without<Cauldroned>)>,
+	mut cauldron_query: Query<(&GlobalTransform, &mut Cauldron, &mut Simmer)>,
+	game_assets: Res<GameAssets>,
+	sound: Res<Audio>,
+) {
+	for (ladle_transform, mut ladle) in &mut ladle_query {
+		let bowl_pos = ladle_transform.transform_point(LADLE_BOWL_OFFSET);
+
+		let dipped_cauldron = cauldron_query
+			.iter_mut()
+			.find(|(cauldron_transform, _, _)| {
+				let cauldron_pos = cauldron_transform.transform_point(Vec3::new(0.0, 0.45, 0.0));
+
+				(cauldron_pos.xy() - bowl_pos.xy()).length_squared() <= 0.5
+					&& bowl_pos.y < cauldron_pos.y
+			});
 
-	let lerped = color.lerp(desired_color, 1.0 - 0.3f32.powf(time.delta_seconds()));
+		let Some((cauldron_transform, mut cauldron, mut simmer)) = dipped_cauldron else {
+			ladle.in_cauldron = false;
+			continue;
+		};
+
+		if ladle.in_cauldron {
+			continue;
+		}
+		ladle.in_cauldron = true;
+
+		let Some((ingredient, scoopable)) = cauldron.scoop() else { continue; };
 
-	material.color = Color::rgb(lerped.x, lerped.y, lerped.z);
+		if cauldron.ingredients.is_empty() {
+			simmer.time = 0.0;
+		}
+
+		// Throw it over the rim on the ladle's side, so it doesn't fall right back in
+		let cauldron_pos = cauldron_transform.transform_point(Vec3::new(0.0, 0.45, 0.0));
+		let side = (bowl_pos.x - cauldron_pos.x).signum();
+
+		commands.spawn((
+			Name::new(ingredient.name.clone()),
+			InventoryItemBundle {
+				scene: scoopable.scene,
+				transform: Transform::from_translation(
+					cauldron_pos + Vec3::new(side * 1.0, 0.6, 0.0),
+				)
+				.with_scale(Vec3::splat(0.01)),
+				velocity: Velocity::linear(Vec3::new(side * 2.0, 3.0, 0.0)),
+				inventory_item: Item::Ingredient,
+				item_size: ItemSize::new(scoopable.size, false),
+				collider: scoopable.collider,
+				..default()
+			},
+			ingredient,
+			scoopable.materials,
+			Damping {
+				linear_damping: 0.5,
+				angular_damping: 0.7,
+			},
+		));
+
+		sound.play(game_assets.sploosh_sound.clone());
+	}
 }
 
 fn consume_cauldroned(
 	mut commands: Commands,
-	mut cauldron_single: Query<(&GlobalTransform, &mut Cauldron)>,
+	mut cauldron_query: Query<(&GlobalTransform, &mut Cauldron, &mut Simmer)>,
 	mut cauldroned_query: Query<
 		(
 			Entity,
+			&Cauldroned,
 			&mut ItemSize,
 			&mut Transform,
 			&mut Item,
 			Option<&mut NamedMaterials>,
 		),
-		(With<Cauldroned>, Without<Cauldron>),
+		Without<Cauldron>,
+	>,
+	ingridient_query: Query<
+		(&Ingredient, &Handle<Scene>, &Collider),
+		(Without<DroppedItem>, With<Item>),
 	>,
-	ingridient_query: Query<&Ingredient, (Without<DroppedItem>, With<Item>)>,
 	time: Res<Time>,
-	_cauldron_mat: Option<Res<CauldronLiquidMaterial>>,
 	_game_assets: Res<GameAssets>,
 	_sound: Res<Audio>,
 ) {
@@ -504,14 +880,24 @@ fn consume_cauldroned(
 		return;
 	}
 
-	let (cauldron_transform, mut cauldron) = cauldron_single.single_mut();
+	for (entity, cauldroned, mut item_size, mut transform, mut item, named_mats) in
+		&mut cauldroned_query
+	{
+		let Ok((cauldron_transform, mut cauldron, mut simmer)) = cauldron_query.get_mut(cauldroned.0) else {
+			// Cauldron is gone, let the item go
+			commands
+				.entity(entity)
+				.remove::<ColliderDisabled>()
+				.remove::<Cauldroned>();
+			item_size.shrinking = false;
+			continue;
+		};
 
-	let cauldron_pos = cauldron_transform
-		.compute_matrix()
-		.mul_vec4(Vec4::new(0.0, 0.3, 0.0, 1.0))
-		.xyz();
+		let cauldron_pos = cauldron_transform
+			.compute_matrix()
+			.mul_vec4(Vec4::new(0.0, 0.3, 0.0, 1.0))
+			.xyz();
 
-	for (entity, mut item_size, mut transform, mut item, named_mats) in &mut cauldroned_query {
 		if item_size.current_size() < 0.08 {
 			match item.as_mut() {
 				Item::AlchemyTool => {
@@ -526,9 +912,17 @@ fn consume_cauldroned(
 					//sound.play(game_assets.blah_sound.clone());
 				}
 				Item::Ingredient => {
-					let ingridient = ingridient_query.get(entity).unwrap().clone();
-
-					cauldron.0.push(ingridient);
+					let (ingridient, scene, collider) = ingridient_query.get(entity).unwrap();
+
+					cauldron.add_ingredient(
+						ingridient.clone(),
+						ScoopableIngredient {
+							scene: scene.clone(),
+							materials: named_mats.map(|m| m.clone()).unwrap_or_default(),
+							collider: collider.clone(),
+							size: item_size.size_mult,
+						},
+					);
 
 					commands.entity(entity).despawn_recursive();
 				}
@@ -539,29 +933,25 @@ fn consume_cauldroned(
 						.remove::<Cauldroned>();
 					item_size.shrinking = false;
 
-					let color = if cauldron.0.is_empty() {
-						Vec3::new(0.6, 0.8, 0.97)
-					} else {
-						cauldron
-							.0
-							.iter()
-							.map(|i| Vec4::from(i.color.as_rgba_f32()).xyz())
-							.sum::<Vec3>() / cauldron.0.len() as f32
-					};
-
+					let color = cauldron.liquid_color();
 					let color = Color::rgb(color.x, color.y, color.z);
 
 					// This is so bad, I'm so so sorry
 					// I don't even know how half of my code works myself..
 					named_mats.unwrap().0[0].material.color = color;
 
-					let mut new_vec = smallvec![];
-					std::mem::swap(&mut new_vec, &mut cauldron.0);
+					let (ingridients, dose) = cauldron.take_dose();
 
 					*potion = Potion::Filled {
-						ingridients: new_vec.into_vec(),
+						ingridients,
 						color,
+						simmer_time: simmer.time,
+						dose,
 					};
+
+					if cauldron.ingredients.is_empty() {
+						simmer.time = 0.0;
+					}
 				}
 			}
 		} else {
@@ -627,7 +1017,7 @@ fn check_eaten(
 // Why another system? Cauldon ate itself and made a hole in space time. (Too lazy to fix)
 fn check_cauldroned(
 	mut commands: Commands,
-	cauldron_query: Query<&GlobalTransform, With<Cauldron>>,
+	cauldron_query: Query<(Entity, &GlobalTransform), With<Cauldron>>,
 	mut item_query: Query<
 		(
 			Entity,
@@ -650,11 +1040,6 @@ fn check_cauldroned(
 	time: Res<Time>,
 ) {
 	let mut grabber = grabber_query.single_mut();
-	let Ok(cauldron_gt) = cauldron_query.get_single() else { return; };
-	let cauldron_pos = cauldron_gt
-		.compute_matrix()
-		.mul_vec4(Vec4::new(0.0, 0.45, 0.0, 1.0))
-		.xyz();
 
 	for (item_entity, global_transform, mut item_size, local_com, item, mut velocity) in
 		&mut item_query
@@ -664,18 +1049,29 @@ fn check_cauldroned(
 			.mul_vec4(local_com.0.extend(1.0))
 			.xyz();
 
-		let relative_vector = cauldron_pos.xy().extend(0.0) - global_com.xy().extend(0.0);
-		let length = relative_vector.length_squared();
-
-		if length > 0.50 {
-			// || (relative_vector.y < 0.0 && length > 0.4) {
+		// Closest cauldron, that the item can get into
+		let Some((cauldron, cauldron_gt, _)) = cauldron_query
+			.iter()
+			.map(|(cauldron, cauldron_gt)| {
+				let cauldron_pos = cauldron_gt
+					.compute_matrix()
+					.mul_vec4(Vec4::new(0.0, 0.45, 0.0, 1.0))
+					.xyz();
+
+				let relative_vector = cauldron_pos.xy().extend(0.0) - global_com.xy().extend(0.0);
+
+				(cauldron, cauldron_gt, relative_vector.length_squared())
+			})
+			.filter(|(_, _, length)| *length <= 0.50)
+			.min_by(|a, b| a.2.total_cmp(&b.2))
+		else {
 			continue;
-		}
+		};
 
 		match item {
 			Item::Potion(Potion::Filled { .. }) | Item::AlchemyTool => {
 				velocity.linvel += cauldron_gt.up() * time.delta_seconds() * 12.0;
-				return;
+				continue;
 			}
 			_ => (),
 		};
@@ -688,7 +1084,7 @@ fn check_cauldroned(
 
 		commands
 			.entity(item_entity)
-			.insert(Cauldroned)
+			.insert(Cauldroned(cauldron))
 			.insert(ColliderDisabled)
 			.insert(Velocity::zero());
 		item_size.shrinking = true;
@@ -750,6 +1146,7 @@ fn mash_ingredient(
 	>,
 	mortar_query: Query<(&GlobalTransform, &CenterOfMass, &Mortar)>,
 	game_assets: Res<GameAssets>,
+	grind_assets: Res<GrindAssets>,
 	mut last_sound_time: Local<f32>,
 	sound: Res<Audio>,
 	time: Res<Time>,
@@ -766,40 +1163,48 @@ fn mash_ingredient(
 				.xyz();
 			let distance = mortar_world_com.distance_squared(item_world_com);
 
-			if distance < 0.35 {
-				if let Grind::Grinding(amount) = &mut ingredient.grind {
-					*amount += time.delta_seconds();
-
-					if *last_sound_time < time.elapsed_seconds() - 0.5 {
-						*last_sound_time = time.elapsed_seconds();
-						sound.play(
-							game_assets
-								.grind_sound
-								.choose(&mut thread_rng())
-								.unwrap()
-								.clone(),
-						);
-					}
+			if distance > 0.35 {
+				continue;
+			}
 
-					if *amount > 0.35 {
-						commands
-							.entity(entity)
-							.insert(game_assets.crushed_ingredient_scene.clone())
-							.insert(NamedMaterials(smallvec![NamedMaterial::new(
-								"Mashed",
-								ingredient.color
-							)]))
-							.remove::<SceneInstanceReady>()
-							.insert(Collider::compound(vec![(
-								Vec3::new(0.0, 0.15, 0.0),
-								Quat::IDENTITY,
-								Collider::round_cone(0.132, 0.225, 0.05),
-							)]));
-
-						ingredient.grind = Grind::Grinded;
-					}
-				}
+			let Some((next_grind, grind_time)) = ingredient.grind.next() else { continue; };
+
+			ingredient.grind_progress += time.delta_seconds();
+
+			if *last_sound_time < time.elapsed_seconds() - 0.5 {
+				*last_sound_time = time.elapsed_seconds();
+				sound.play(
+					game_assets
+						.grind_sound
+						.choose(&mut thread_rng())
+						.unwrap()
+						.clone(),
+				);
+			}
+
+			if ingredient.grind_progress < grind_time {
+				continue;
 			}
+
+			let Some((scene, collider)) = grind_assets.get(next_grind) else { continue; };
+
+			commands
+				.entity(entity)
+				.insert(scene.clone())
+				.insert(NamedMaterials(smallvec![NamedMaterial::new(
+					"Mashed",
+					ingredient.color
+				)]))
+				.remove::<SceneInstanceReady>()
+				.insert(collider.clone());
+
+			ingredient.grind = next_grind;
+			ingredient.grind_progress = 0.0;
+
+			sound.play(match next_grind {
+				Grind::Paste => game_assets.blah_sound.clone(),
+				_ => game_assets.grind_done_sound.clone(),
+			});
 		}
 	}
 }
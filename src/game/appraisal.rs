@@ -0,0 +1,150 @@
+use bevy_inspector_egui::bevy_egui::{egui, EguiContexts};
+
+use crate::prelude::*;
+
+use super::{
+	backpack::InventoryCamera,
+	effects::{Effect, EffectQuality, EffectRegistry},
+	items::{Grabber, Item, Potion},
+	journal::RecipeJournal,
+};
+
+pub struct AppraisalPlugin;
+impl Plugin for AppraisalPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_system(appraise_hovered_potion.in_set(OnUpdate(GameState::InGame)));
+	}
+}
+
+/// How well the player can read a potion, grows with every drunk potion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Experience {
+	Novice,
+	Apprentice,
+	Master,
+}
+
+impl Experience {
+	fn from_journal(journal: &RecipeJournal) -> Self {
+		match journal.0.len() {
+			0..=2 => Experience::Novice,
+			3..=9 => Experience::Apprentice,
+			_ => Experience::Master,
+		}
+	}
+}
+
+fn appraise_hovered_potion(
+	mut contexts: EguiContexts,
+	item_query: Query<&Item>,
+	parent_query: Query<&Parent>,
+	grabber_query: Query<&Grabber>,
+	rapier_context: Res<RapierContext>,
+	inventory_camera: Query<(&GlobalTransform, &Camera), With<InventoryCamera>>,
+	windows: Query<&Window>,
+	journal: Res<RecipeJournal>,
+	effect_registry: Res<EffectRegistry>,
+) {
+	// Don't get in the way while moving stuff around
+	if grabber_query.iter().any(|g| g.grabbed_entity.is_some()) {
+		return;
+	}
+
+	let Ok(window) = windows.get_single() else { return; };
+	let Some(mouse_position) = window.cursor_position() else { return; };
+	let Ok((camera_transform, camera)) = inventory_camera.get_single() else { return; };
+	let Some(ray) = camera.viewport_to_world(camera_transform, mouse_position) else { return; };
+
+	let filter = QueryFilter::only_dynamic()
+		.exclude_sensors()
+		.groups(CollisionGroups::new(Group::GROUP_2, Group::GROUP_2));
+	let Some((entity, _)) = rapier_context.cast_ray(ray.origin, ray.direction, 100.0, true, filter) else { return; };
+
+	// Collider might be somewhere down in the scene
+	let Some(item) = std::iter::once(entity)
+		.chain(parent_query.iter_ancestors(entity))
+		.find_map(|e| item_query.get(e).ok())
+	else {
+		return;
+	};
+
+	let Item::Potion(potion) = item else { return; };
+	let Potion::Filled { ingridients, .. } = potion else { return; };
+	let Some((quality, potency, effects)) = potion.brew(&effect_registry) else { return; };
+
+	let experience = Experience::from_journal(&journal);
+
+	egui::show_tooltip_at_pointer(contexts.ctx_mut(), egui::Id::new("appraisal"), |ui| {
+		ui.strong(format!("Potion of {} ingredients", ingridients.len()));
+
+		match experience {
+			Experience::Novice => {
+				ui.label(format!(
+					"Looks {}",
+					describe(quality, ["murky", "cloudy", "clear"])
+				));
+				ui.label(format!(
+					"Smells {}",
+					describe(potency, ["faint", "sharp", "pungent"])
+				));
+			}
+			Experience::Apprentice => {
+				// Rounded to quarters, good enough for a rough guess
+				ui.label(format!("Quality: ~{:.2}", (quality * 4.0).round() / 4.0));
+				ui.label(format!("Potency: ~{:.2}", (potency * 4.0).round() / 4.0));
+			}
+			Experience::Master => {
+				ui.label(format!("Quality: {quality:.2}"));
+				ui.label(format!("Potency: {potency:.2}"));
+			}
+		}
+
+		ui.separator();
+
+		if experience == Experience::Novice {
+			ui.weak("You can't tell what it does yet. Drink a few more potions.");
+			return;
+		}
+
+		if effects.is_empty() {
+			ui.label("Probably does nothing");
+			return;
+		}
+
+		for (bucket, count) in forecast(&effects, &effect_registry) {
+			match experience {
+				Experience::Master => ui.label(format!("{count}x {bucket:?}")),
+				_ => ui.label(format!("Something {bucket:?}")),
+			};
+		}
+	});
+}
+
+fn describe(value: f32, words: [&'static str; 3]) -> &'static str {
+	match value {
+		v if v < 0.4 => words[0],
+		v if v < 0.7 => words[1],
+		_ => words[2],
+	}
+}
+
+/// How many effects of each quality the potion will have, best first
+fn forecast(
+	effects: &[Effect],
+	registry: &EffectRegistry,
+) -> SmallVec<[(EffectQuality, usize); 5]> {
+	EffectQuality::ALL
+		.iter()
+		.rev()
+		.map(|bucket| {
+			(
+				*bucket,
+				effects
+					.iter()
+					.filter(|e| registry.quality(e.effect) == *bucket)
+					.count(),
+			)
+		})
+		.filter(|(_, count)| *count > 0)
+		.collect()
+}
@@ -21,6 +21,13 @@ pub struct Inventory(pub Vec<Entity>);
 #[derive(Component)]
 pub struct Backpack;
 
+/// Resting transform of the backpack
+pub const BACKPACK_TRANSFORM: Transform = Transform {
+	translation: Vec3::new(0.0, -1.5, 0.0),
+	rotation: Quat::IDENTITY,
+	scale: Vec3::new(3.0, 2.6, 3.0),
+};
+
 #[derive(Component)]
 pub struct InventoryCamera;
 
@@ -61,12 +68,13 @@ fn spawn(mut commands: Commands, game_assets: Res<GameAssets>, gltfs: Res<Assets
 	commands.spawn((
 		Backpack,
 		Name::new("Backpack"),
-		RigidBody::Fixed,
+		// Kinematic, so it can push the items around when it moves
+		RigidBody::KinematicPositionBased,
 		RenderLayers::layer(2),
 		CollisionGroups::new(Group::GROUP_2, Group::GROUP_2 | Group::GROUP_5),
 		SceneBundle {
 			scene: backpack.scenes[1].clone(),
-			transform: Transform::from_xyz(0.0, -1.5, 0.0).with_scale(Vec3::new(3.0, 2.6, 3.0)),
+			transform: BACKPACK_TRANSFORM,
 			..default()
 		},
 		NamedMaterials::backpack(),
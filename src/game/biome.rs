@@ -0,0 +1,248 @@
+use bevy::render::{mesh::Indices, render_resource::PrimitiveTopology};
+use bevy_inspector_egui::egui::lerp;
+
+use crate::{
+	assets::{Spawnable, SpawnableArchetype},
+	prelude::*,
+};
+
+use super::world::{WorldRng, ISLAND_SIZE};
+
+/// Region of the island, decides what grows there
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Biome {
+	Forest,
+	MushroomGrove,
+	BerryMeadow,
+	BarrenRock,
+}
+
+impl Biome {
+	pub const ALL: [Biome; 4] = [
+		Biome::Forest,
+		Biome::MushroomGrove,
+		Biome::BerryMeadow,
+		Biome::BarrenRock,
+	];
+
+	/// Island grass already has this color, so it doesn't get its own ground
+	pub const ISLAND_GRASS: Biome = Biome::BerryMeadow;
+
+	/// Weight of the spawnable in this biome's spawn table
+	pub fn spawn_weight(self, spawnable: &Spawnable) -> f32 {
+		use Biome::*;
+		use SpawnableArchetype::*;
+
+		let berries = spawnable.ingredient.is_some();
+
+		let multiplier = match (self, spawnable.archetype) {
+			(Forest, Tree) => 2.5,
+			(Forest, Bush) if berries => 0.4,
+			(Forest, Bush) => 0.8,
+			(Forest, Mushroom) => 0.6,
+			(Forest, Root) => 1.0,
+			(Forest, Plant) => 0.5,
+			(MushroomGrove, Tree) => 0.5,
+			(MushroomGrove, Bush) => 0.3,
+			(MushroomGrove, Mushroom) => 5.0,
+			(MushroomGrove, Root) => 0.5,
+			(MushroomGrove, Plant) => 0.3,
+			(BerryMeadow, Tree) => 0.1,
+			(BerryMeadow, Bush) if berries => 5.0,
+			(BerryMeadow, Bush) => 1.0,
+			(BerryMeadow, Mushroom) => 0.4,
+			(BerryMeadow, Root) => 0.5,
+			(BerryMeadow, Plant) => 3.0,
+			(BarrenRock, Tree) => 0.05,
+			(BarrenRock, Bush) => 0.3,
+			(BarrenRock, Mushroom) => 1.0,
+			(BarrenRock, Root) => 2.0,
+			(BarrenRock, Plant) => 0.3,
+		};
+
+		spawnable.spawn_weight * multiplier
+	}
+
+	pub fn ground_color(self) -> Color {
+		match self {
+			Biome::Forest => Color::rgb(0.3, 0.5, 0.12),
+			Biome::MushroomGrove => Color::rgb(0.42, 0.38, 0.48),
+			Biome::BerryMeadow => Color::YELLOW_GREEN,
+			Biome::BarrenRock => Color::rgb(0.52, 0.48, 0.4),
+		}
+	}
+
+	/// Multiplies the chance of a spawnable being rare
+	pub fn rare_chance(self) -> f32 {
+		match self {
+			Biome::Forest => 1.0,
+			Biome::MushroomGrove => 1.5,
+			Biome::BerryMeadow => 1.0,
+			Biome::BarrenRock => 3.0,
+		}
+	}
+
+	/// Chance, that something grows at a spot at all
+	pub fn density(self) -> f32 {
+		match self {
+			Biome::BarrenRock => 0.3,
+			_ => 1.0,
+		}
+	}
+}
+
+/// Smooth random values, same seed always gives the same noise
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ValueNoise {
+	pub seed: u64,
+	/// Distance between the random values, bigger makes smoother noise
+	pub scale: f32,
+}
+
+impl ValueNoise {
+	fn lattice(&self, x: i32, y: i32) -> f32 {
+		let mut hash = self.seed
+			^ (x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+			^ (y as u32 as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+
+		hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		hash ^= hash >> 31;
+
+		(hash >> 40) as f32 / (1u64 << 24) as f32
+	}
+
+	/// Between 0.0 and 1.0
+	pub fn get(&self, position: Vec2) -> f32 {
+		let position = position / self.scale;
+		let cell = position.floor();
+		let t = position - cell;
+		let t = t * t * (3.0 - 2.0 * t);
+
+		let (x, y) = (cell.x as i32, cell.y as i32);
+		let top = lerp(self.lattice(x, y)..=self.lattice(x + 1, y), t.x);
+		let bottom = lerp(self.lattice(x, y + 1)..=self.lattice(x + 1, y + 1), t.x);
+
+		lerp(top..=bottom, t.y)
+	}
+}
+
+/// Where the biomes are. Every biome has its own noise, and the strongest one wins.
+#[derive(Resource, Debug, Default, PartialEq)]
+pub struct BiomeMap {
+	noise: [(ValueNoise, ValueNoise); 4],
+}
+
+impl BiomeMap {
+	pub fn new(rng: &mut impl Rng) -> Self {
+		BiomeMap {
+			noise: Biome::ALL.map(|_| {
+				(
+					ValueNoise {
+						seed: rng.gen(),
+						scale: 70.0,
+					},
+					// Makes the borders a bit ragged
+					ValueNoise {
+						seed: rng.gen(),
+						scale: 15.0,
+					},
+				)
+			}),
+		}
+	}
+
+	pub fn biome_at(&self, position: Vec2) -> Biome {
+		Biome::ALL
+			.into_iter()
+			.zip(self.noise.iter())
+			.map(|(biome, (base, detail))| {
+				(biome, base.get(position) * 0.8 + detail.get(position) * 0.2)
+			})
+			.max_by(|a, b| a.1.total_cmp(&b.1))
+			.map(|(biome, _)| biome)
+			.unwrap_or(Biome::ISLAND_GRASS)
+	}
+}
+
+pub fn generate_biome_map(mut biome_map: ResMut<BiomeMap>, mut world_rng: ResMut<WorldRng>) {
+	*biome_map = BiomeMap::new(&mut world_rng.0);
+}
+
+/// Colors the ground of every biome, that doesn't look like the island grass.
+/// Top of the island is flat at y = 0 (spawnables are placed there too),
+/// so the ground is just flat quads lying on it. A bumpy island would need another way.
+pub fn spawn_biome_ground(
+	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut foliage_mat: ResMut<Assets<FoliageMaterial>>,
+	biome_map: Res<BiomeMap>,
+) {
+	const CELL_SIZE: f32 = 2.0;
+	const RADIUS: f32 = ISLAND_SIZE - 5.0;
+
+	let cells = (RADIUS / CELL_SIZE).ceil() as i32;
+
+	for biome in Biome::ALL {
+		if biome == Biome::ISLAND_GRASS {
+			continue;
+		}
+
+		let mut positions: Vec<[f32; 3]> = vec![];
+		let mut indices: Vec<u32> = vec![];
+
+		let mut add_quad = |start: f32, end: f32, z: f32| {
+			let index = positions.len() as u32;
+			positions.extend([
+				[start, 0.01, z],
+				[end, 0.01, z],
+				[end, 0.01, z + CELL_SIZE],
+				[start, 0.01, z + CELL_SIZE],
+			]);
+			indices.extend([index, index + 3, index + 2, index, index + 2, index + 1]);
+		};
+
+		// Cells next to each other in a row are merged into one quad
+		for z in -cells..cells {
+			let z = z as f32 * CELL_SIZE;
+			let mut run_start = None;
+
+			for x in -cells..=cells {
+				let x = x as f32 * CELL_SIZE;
+				let center = Vec2::new(x, z) + CELL_SIZE * 0.5;
+
+				let inside = x < cells as f32 * CELL_SIZE
+					&& center.length_squared() < RADIUS.powi(2)
+					&& biome_map.biome_at(center) == biome;
+
+				match (inside, run_start) {
+					(true, None) => run_start = Some(x),
+					(false, Some(start)) => {
+						add_quad(start, x, z);
+						run_start = None;
+					}
+					_ => {}
+				}
+			}
+		}
+
+		let vertex_count = positions.len();
+		let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+		mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+		mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; vertex_count]);
+		mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; vertex_count]);
+		mesh.set_indices(Some(Indices::U32(indices)));
+
+		commands.spawn((
+			Name::new(format!("{biome:?} Ground")),
+			MaterialMeshBundle {
+				mesh: meshes.add(mesh),
+				material: foliage_mat.add(FoliageMaterial {
+					color: biome.ground_color(),
+					sss: false,
+				}),
+				..default()
+			},
+		));
+	}
+}
@@ -1,32 +1,388 @@
 use bevy::math::Vec3Swizzles;
 use bevy_inspector_egui::egui::lerp;
+use serde::{Serialize, Serializer};
+use std::{fmt, ops::Range};
 
-use crate::{choice, prelude::*};
+use crate::{
+	assets::{Spawnable, SpawnableArchetype},
+	prelude::*,
+};
 
 use super::{
-	ingredient::{Grind, Ingredient},
-	items::{DroppedItem, Item},
+	backpack::{Backpack, BACKPACK_TRANSFORM},
+	hallucinations::Phantom,
+	ingredient::Ingredient,
+	items::{DroppedItem, Item, ItemSize},
 	player::Player,
-	world::SpawnableInstance,
+	world::{spawn_ball, Ball, BallAssets, SpawnableInstance},
 };
 
 pub struct EffectsPlugin;
 impl Plugin for EffectsPlugin {
 	fn build(&self, app: &mut App) {
-		app.add_systems((spawn_arrows,).in_schedule(OnEnter(GameState::InGame)))
+		use EffectStage::*;
+
+		app.insert_resource(ActiveEffects::default())
+			.init_resource::<EffectRegistry>()
+			.add_event::<EffectStarted>()
+			.add_event::<EffectRefreshed>()
+			.add_event::<EffectEnded>()
+			.configure_sets((Expire, Apply, Tick).chain().after(effect_tick))
+			.add_systems((spawn_arrows,).in_schedule(OnEnter(GameState::InGame)))
 			.add_systems(
-				(effect_tick, rotate_arrow, gravity_effects, earthquake)
+				(effect_tick, despawn_fallen_balls, animate_backflip)
 					.in_set(OnUpdate(GameState::InGame)),
 			)
-			.insert_resource(ActiveEffects::default());
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::INVISIBLE_INGRIDIENTS),
+				color: Color::rgb(1.0, 0.85, 0.3),
+				..EffectDef::new(EffectType::ARROW, EffectQuality::Positive)
+			})
+			.add_effect_system(EffectType::ARROW, Tick, point_arrow)
+			.add_effect_system(EffectType::ARROW, Expire, hide_arrow::<Arrow>)
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::HALLUCINATIONS),
+				color: Color::rgb(1.0, 0.6, 0.1),
+				..EffectDef::new(EffectType::RARE_ARROWS, EffectQuality::Exceptional)
+			})
+			.add_effect_system(EffectType::RARE_ARROWS, Tick, point_rare_arrow)
+			.add_effect_system(EffectType::RARE_ARROWS, Expire, hide_arrow::<RareArrow>)
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::GOD_MODE),
+				color: Color::rgb(0.5, 0.3, 0.15),
+				..EffectDef::new(EffectType::EARTHQUAKE, EffectQuality::Catastrophic)
+			})
+			.add_effect_system(EffectType::EARTHQUAKE, Tick, earthquake)
+			.add_effect(EffectDef {
+				color: Color::rgb(1.0, 0.4, 0.7),
+				..EffectDef::new(EffectType::SPAWN_BALL, EffectQuality::Neutral)
+			})
+			.add_effect_system(EffectType::SPAWN_BALL, Tick, spawn_balls)
+			.add_effect(EffectDef {
+				color: Color::rgb(0.6, 0.45, 0.3),
+				..EffectDef::new(EffectType::BACKPACK_BACKFLIP, EffectQuality::Negative)
+			})
+			.add_effect_system(EffectType::BACKPACK_BACKFLIP, Apply, start_backflips)
+			.add_effect_system(EffectType::BACKPACK_BACKFLIP, Tick, backpack_backflip)
+			.add_effect(EffectDef {
+				color: Color::rgb(0.7, 0.7, 0.75),
+				..EffectDef::new(EffectType::SMALL_INSTRUMENTS, EffectQuality::Negative)
+			})
+			.add_effect_system(EffectType::SMALL_INSTRUMENTS, Tick, small_instruments)
+			.add_effect_system(EffectType::SMALL_INSTRUMENTS, Expire, normal_instruments)
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::ARROW),
+				color: Color::rgba(0.9, 0.9, 1.0, 0.4),
+				..EffectDef::new(EffectType::INVISIBLE_INGRIDIENTS, EffectQuality::Negative)
+			})
+			.add_effect_system(
+				EffectType::INVISIBLE_INGRIDIENTS,
+				Tick,
+				invisible_ingredients,
+			)
+			.add_effect_system(
+				EffectType::INVISIBLE_INGRIDIENTS,
+				Expire,
+				reveal_ingredients,
+			)
+			.add_effect(EffectDef {
+				color: Color::rgb(0.2, 0.6, 0.2),
+				..EffectDef::new(EffectType::TREE_ROCKETS, EffectQuality::Neutral)
+			})
+			.add_effect_system(EffectType::TREE_ROCKETS, Tick, tree_rockets)
+			.add_effect_system(EffectType::TREE_ROCKETS, Expire, land_tree_rockets)
+			.add_effect(EffectDef {
+				stack_rule: StackRule::Replace,
+				group: Some(GRAVITY_GROUP),
+				color: Color::rgb(0.85, 0.85, 1.0),
+				..EffectDef::new(EffectType::NO_GRAVITY, EffectQuality::Neutral)
+			})
+			.add_effect(EffectDef {
+				// Not found in any ingredient
+				weight: 0.0,
+				stack_rule: StackRule::Replace,
+				group: Some(GRAVITY_GROUP),
+				color: Color::rgb(0.6, 0.6, 0.9),
+				..EffectDef::new(EffectType::LOW_GRAVITY, EffectQuality::Neutral)
+			})
+			.add_effect(EffectDef {
+				stack_rule: StackRule::Replace,
+				group: Some(GRAVITY_GROUP),
+				color: Color::rgb(0.7, 0.3, 0.9),
+				..EffectDef::new(EffectType::WHACKY_GRAVITY, EffectQuality::Neutral)
+			})
+			.add_effect_system(EffectType::WHACKY_GRAVITY, Tick, whacky_gravity)
+			.add_effect_system(EffectType::LOW_GRAVITY, Tick, low_gravity)
+			.add_effect_system(EffectType::NO_GRAVITY, Tick, no_gravity);
+
+		for gravity in [
+			EffectType::NO_GRAVITY,
+			EffectType::LOW_GRAVITY,
+			EffectType::WHACKY_GRAVITY,
+		] {
+			app.add_effect_system(gravity, Expire, reset_gravity);
+		}
+	}
+}
+
+/// Only one gravity effect can be active at a time
+const GRAVITY_GROUP: &str = "Gravity";
+
+/// Name of an effect in the `EffectRegistry`.
+/// Built-in effects are associated consts, declared next to the code that makes them work.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Reflect, FromReflect)]
+#[reflect_value(PartialEq, Hash)]
+pub struct EffectType(pub &'static str);
+
+impl EffectType {
+	pub const ARROW: EffectType = EffectType("Arrow");
+	pub const RARE_ARROWS: EffectType = EffectType("RareArrows");
+	pub const EARTHQUAKE: EffectType = EffectType("Earthquake");
+	pub const SPAWN_BALL: EffectType = EffectType("SpawnBall");
+	pub const BACKPACK_BACKFLIP: EffectType = EffectType("BackpackBackflip");
+	pub const SMALL_INSTRUMENTS: EffectType = EffectType("SmallInstruments");
+	pub const INVISIBLE_INGRIDIENTS: EffectType = EffectType("InvisibleIngridients");
+	pub const TREE_ROCKETS: EffectType = EffectType("TreeRockets");
+	pub const NO_GRAVITY: EffectType = EffectType("NoGravity");
+	pub const LOW_GRAVITY: EffectType = EffectType("LowGravity");
+	pub const WHACKY_GRAVITY: EffectType = EffectType("WhackyGravity");
+}
+
+impl fmt::Debug for EffectType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+// Saved like the variant of an enum, same as the old `EffectType` enum was.
+// There is no `Deserialize`, saved names are looked up with `EffectRegistry::find`.
+impl Serialize for EffectType {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_unit_variant("EffectType", 0, self.0)
+	}
+}
+
+/// Everything the game needs to know about an effect
+#[derive(Debug, Clone)]
+pub struct EffectDef {
+	pub effect: EffectType,
+	/// Tier the effect is generated in
+	pub quality: EffectQuality,
+	/// How likely it is picked over other effects of the same quality. Zero never generates it.
+	pub weight: f32,
+	/// Seconds an ingredient with the effect adds to the brew
+	pub duration: Range<f32>,
+	/// What happens when the effect is applied while it (or its opposite) is already active
+	pub stack_rule: StackRule,
+	/// Effect that cancels this one out when they are brewed together
+	pub opposite: Option<EffectType>,
+	/// With `StackRule::Replace`, replaces any active effect of the same group too
+	pub group: Option<&'static str>,
+	/// Shown in the HUD, since there is no text or icons for effects
+	pub color: Color,
+}
+
+impl EffectDef {
+	pub fn new(effect: EffectType, quality: EffectQuality) -> Self {
+		Self {
+			effect,
+			quality,
+			weight: 1.0,
+			duration: 30.0..120.0,
+			stack_rule: StackRule::Refresh,
+			opposite: None,
+			group: None,
+			color: Color::WHITE,
+		}
+	}
+}
+
+/// All effects known to the game, in the order they were added
+#[derive(Resource, Debug, Clone, Default)]
+pub struct EffectRegistry(pub Vec<EffectDef>);
+
+impl EffectRegistry {
+	pub fn get(&self, effect: EffectType) -> Option<&EffectDef> {
+		self.0.iter().find(|d| d.effect == effect)
+	}
+
+	/// Effect with the name, if it's still in the game
+	pub fn find(&self, name: &str) -> Option<EffectType> {
+		self.0.iter().map(|d| d.effect).find(|e| e.0 == name)
+	}
+
+	/// Unknown effects (like ones from an old save) are neutral
+	pub fn quality(&self, effect: EffectType) -> EffectQuality {
+		self.get(effect)
+			.map(|d| d.quality)
+			.unwrap_or(EffectQuality::Neutral)
+	}
+
+	pub fn stack_rule(&self, effect: EffectType) -> StackRule {
+		self.get(effect)
+			.map(|d| d.stack_rule)
+			.unwrap_or(StackRule::Refresh)
 	}
+
+	pub fn opposite(&self, effect: EffectType) -> Option<EffectType> {
+		self.get(effect).and_then(|d| d.opposite)
+	}
+
+	pub fn group(&self, effect: EffectType) -> Option<&'static str> {
+		self.get(effect).and_then(|d| d.group)
+	}
+
+	pub fn color(&self, effect: EffectType) -> Color {
+		self.get(effect).map(|d| d.color).unwrap_or(Color::WHITE)
+	}
+
+	/// Picks a random effect of the quality, according to their weights
+	pub fn generate(&self, quality: EffectQuality, rng: &mut impl Rng) -> Option<&EffectDef> {
+		let (choices, weights): (Vec<&EffectDef>, Vec<f32>) = self
+			.0
+			.iter()
+			.filter(|d| d.quality == quality && d.weight > 0.0)
+			.map(|d| (d, d.weight))
+			.unzip();
+
+		if choices.is_empty() {
+			return None;
+		}
+
+		Choices {
+			choices: &choices,
+			weights: Some(&weights),
+		}
+		.get_random(rng)
+		.copied()
+	}
+}
+
+/// When an effect system runs. Stages run in this order, right after `effect_tick`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectStage {
+	/// On `EffectEnded`
+	Expire,
+	/// On `EffectStarted`
+	Apply,
+	/// Every frame while the effect is active
+	Tick,
+}
+
+/// Plugin style API, so effects can be added from anywhere in the game
+pub trait EffectAppExt {
+	/// Makes the effect known, so it can be generated in ingredients
+	fn add_effect(&mut self, def: EffectDef) -> &mut Self;
+
+	/// Runs the system for the effect, in the given stage
+	fn add_effect_system<M>(
+		&mut self,
+		effect: EffectType,
+		stage: EffectStage,
+		system: impl IntoSystemConfig<M>,
+	) -> &mut Self;
+}
+
+impl EffectAppExt for App {
+	fn add_effect(&mut self, def: EffectDef) -> &mut Self {
+		let mut registry = self
+			.world
+			.get_resource_or_insert_with(EffectRegistry::default);
+
+		if registry.get(def.effect).is_some() {
+			warn!("Effect {:?} was added twice", def.effect);
+			return self;
+		}
+
+		registry.0.push(def);
+		self
+	}
+
+	fn add_effect_system<M>(
+		&mut self,
+		effect: EffectType,
+		stage: EffectStage,
+		system: impl IntoSystemConfig<M>,
+	) -> &mut Self {
+		let system = system.in_set(stage).in_set(OnUpdate(GameState::InGame));
+
+		match stage {
+			EffectStage::Expire => self.add_system(system.run_if(effect_ended(effect))),
+			EffectStage::Apply => self.add_system(system.run_if(effect_started(effect))),
+			EffectStage::Tick => self.add_system(system.run_if(effect_active(effect))),
+		}
+	}
+}
+
+/// Effect was added, while it wasn't active
+#[derive(Debug, Clone, Copy)]
+pub struct EffectStarted(pub Effect);
+
+/// Already active effect got stronger, weaker or longer
+#[derive(Debug, Clone, Copy)]
+pub struct EffectRefreshed(pub Effect);
+
+/// Effect wore off, or was removed
+#[derive(Debug, Clone, Copy)]
+pub struct EffectEnded(pub EffectType);
+
+pub fn effect_active(effect: EffectType) -> impl FnMut(Res<ActiveEffects>) -> bool + Clone {
+	move |active_effects: Res<ActiveEffects>| active_effects.has_effect(effect).is_some()
 }
 
-pub fn effect_tick(mut effects: ResMut<ActiveEffects>, time: Res<Time>) {
+// Conditions read all events, so none are left over for the next frame
+
+pub fn effect_started(
+	effect: EffectType,
+) -> impl FnMut(EventReader<EffectStarted>) -> bool + Clone {
+	move |mut events: EventReader<EffectStarted>| {
+		events.iter().filter(|e| e.0.effect == effect).count() > 0
+	}
+}
+
+pub fn effect_ended(effect: EffectType) -> impl FnMut(EventReader<EffectEnded>) -> bool + Clone {
+	move |mut events: EventReader<EffectEnded>| events.iter().filter(|e| e.0 == effect).count() > 0
+}
+
+/// Counts effects down, and tells everyone what changed since the last frame
+pub fn effect_tick(
+	mut effects: ResMut<ActiveEffects>,
+	mut started_events: EventWriter<EffectStarted>,
+	mut refreshed_events: EventWriter<EffectRefreshed>,
+	mut ended_events: EventWriter<EffectEnded>,
+	time: Res<Time>,
+	mut previous: Local<SmallVec<[Effect; 12]>>,
+) {
+	// Effects are pushed and removed all over the place, so compare with how they were left
+	for effect in &effects.0 {
+		match previous.iter().find(|e| e.effect == effect.effect) {
+			None => started_events.send(EffectStarted(*effect)),
+			Some(old) if old.time_left != effect.time_left || old.potency != effect.potency => {
+				refreshed_events.send(EffectRefreshed(*effect))
+			}
+			_ => {}
+		}
+	}
+	for old in previous.iter() {
+		if effects.has_effect(old.effect).is_none() {
+			ended_events.send(EffectEnded(old.effect));
+		}
+	}
+
 	for effect in effects.0.iter_mut() {
 		effect.time_left -= time.delta_seconds();
+
+		if effect.time_left < 0.0 {
+			ended_events.send(EffectEnded(effect.effect));
+		}
 	}
 	effects.0.retain(|e| e.time_left >= 0.0);
+
+	*previous = effects.0.clone();
 }
 
 #[derive(Component)]
@@ -62,141 +418,143 @@ pub fn spawn_arrows(mut commands: Commands, game_assets: Res<GameAssets>) {
 	));
 }
 
-pub fn rotate_arrow(
-	player: Query<&Transform, (With<Player>, Without<Arrow>, Without<RareArrow>)>,
-	mut arrow: Query<
-		(&mut Transform, &mut Visibility),
-		(With<Arrow>, Without<RareArrow>, Without<Player>),
-	>,
-	mut rare_arrow: Query<
-		(&mut Transform, &mut Visibility),
-		(With<RareArrow>, Without<Arrow>, Without<Player>),
-	>,
+/// Points the arrow towards the closest ingredient
+fn point_arrow(
+	player: Query<&Transform, (With<Player>, Without<Arrow>)>,
+	mut arrow: Query<(&mut Transform, &mut Visibility), (With<Arrow>, Without<Player>)>,
 	ingredient_query: Query<
-		(&Transform, &Ingredient, &SpawnableInstance),
+		&Transform,
 		(
+			With<Ingredient>,
+			With<SpawnableInstance>,
 			Without<DroppedItem>,
+			Without<Phantom>,
 			Without<Player>,
 			Without<Arrow>,
-			Without<RareArrow>,
 		),
 	>,
-	effects: Res<ActiveEffects>,
 	time: Res<Time>,
 ) {
+	let Ok(player_transform) = player.get_single() else { return; };
 	let Ok((mut arrow_transform, mut arrow_visibility)) = arrow.get_single_mut() else { return; };
-	let Ok((mut rare_arrow_transform, mut rare_arrow_visibility)) = rare_arrow.get_single_mut() else { return; };
 
-	let rare_effect = effects.has_effect(EffectType::RareArrows);
-	let effect = effects.has_effect(EffectType::Arrow);
+	steer_arrow(
+		player_transform,
+		&mut arrow_transform,
+		&mut arrow_visibility,
+		ingredient_query.iter(),
+		&time,
+	);
+}
 
-	*arrow_visibility = if effect.is_some() {
-		Visibility::Visible
-	} else {
-		Visibility::Hidden
-	};
-	*rare_arrow_visibility = if rare_effect.is_some() {
-		Visibility::Visible
-	} else {
-		Visibility::Hidden
+/// Points the rare arrow towards the closest rare ingredient
+fn point_rare_arrow(
+	player: Query<&Transform, (With<Player>, Without<RareArrow>)>,
+	mut rare_arrow: Query<(&mut Transform, &mut Visibility), (With<RareArrow>, Without<Player>)>,
+	ingredient_query: Query<
+		(&Transform, &Ingredient),
+		(
+			With<SpawnableInstance>,
+			Without<DroppedItem>,
+			Without<Phantom>,
+			Without<Player>,
+			Without<RareArrow>,
+		),
+	>,
+	time: Res<Time>,
+) {
+	let Ok(player_transform) = player.get_single() else { return; };
+	let Ok((mut arrow_transform, mut arrow_visibility)) = rare_arrow.get_single_mut() else { return; };
+
+	steer_arrow(
+		player_transform,
+		&mut arrow_transform,
+		&mut arrow_visibility,
+		ingredient_query
+			.iter()
+			.filter(|(_, ingredient)| ingredient.is_rare)
+			.map(|(transform, _)| transform),
+		&time,
+	);
+}
+
+fn steer_arrow<'a>(
+	player_transform: &Transform,
+	arrow_transform: &mut Transform,
+	arrow_visibility: &mut Visibility,
+	targets: impl Iterator<Item = &'a Transform>,
+	time: &Time,
+) {
+	let closest = targets
+		.map(|t| {
+			(
+				t,
+				t.translation.distance_squared(player_transform.translation),
+			)
+		})
+		.min_by(|a, b| a.1.total_cmp(&b.1));
+
+	let Some((closest, distance)) = closest else {
+		*arrow_visibility = Visibility::Hidden;
+		return;
 	};
 
-	let player_transform = player.single();
+	*arrow_visibility = Visibility::Visible;
+	arrow_transform.rotation = Quat::slerp(
+		arrow_transform.rotation,
+		Quat::from_rotation_arc(
+			Vec3::Z,
+			(player_transform.translation - closest.translation)
+				.xz()
+				.extend(0.0)
+				.xzy()
+				.normalize(),
+		),
+		1.0 - 0.001f32.powf(time.delta_seconds()),
+	);
+	arrow_transform.translation = player_transform.translation
+		+ arrow_transform.forward() * 1.0f32.min(distance.sqrt() - 1.5);
+}
 
-	if rare_effect.is_some() {
-		let closest_rare = ingredient_query
-			.iter()
-			.filter(|i| i.1.is_rare)
-			.map(|a| {
-				(
-					a.0,
-					a.0.translation
-						.distance_squared(player_transform.translation),
-				)
-			})
-			.min_by(|a, b| a.1.total_cmp(&b.1));
-
-		if let Some((closest, distance)) = closest_rare {
-			rare_arrow_transform.rotation = Quat::slerp(
-				rare_arrow_transform.rotation,
-				Quat::from_rotation_arc(
-					Vec3::Z,
-					(player_transform.translation - closest.translation)
-						.xz()
-						.extend(0.0)
-						.xzy()
-						.normalize(),
-				),
-				1.0 - 0.001f32.powf(time.delta_seconds()),
-			);
-			rare_arrow_transform.translation = player_transform.translation
-				+ rare_arrow_transform.forward() * 1.0f32.min(distance.sqrt() - 1.5);
-		} else {
-			*rare_arrow_visibility = Visibility::Hidden;
-		}
+fn hide_arrow<A: Component>(mut arrow: Query<&mut Visibility, With<A>>) {
+	for mut visibility in &mut arrow {
+		*visibility = Visibility::Hidden;
 	}
+}
 
-	if effect.is_some() {
-		let closest = ingredient_query
-			.iter()
-			.map(|a| {
-				(
-					a.0,
-					a.0.translation
-						.distance_squared(player_transform.translation),
-				)
-			})
-			.min_by(|a, b| a.1.total_cmp(&b.1));
-		if let Some((closest, distance)) = closest {
-			arrow_transform.rotation = Quat::slerp(
-				arrow_transform.rotation,
-				Quat::from_rotation_arc(
-					Vec3::Z,
-					(player_transform.translation - closest.translation)
-						.xz()
-						.extend(0.0)
-						.xzy()
-						.normalize(),
-				),
-				1.0 - 0.001f32.powf(time.delta_seconds()),
-			);
-			arrow_transform.translation = player_transform.translation
-				+ arrow_transform.forward() * 1.0f32.min(distance.sqrt() - 1.5);
-		} else {
-			*arrow_visibility = Visibility::Hidden;
-		}
-	}
+fn no_gravity(mut config: ResMut<RapierConfiguration>) {
+	config.gravity = Vec3::splat(0.0);
 }
 
-fn gravity_effects(active_effects: Res<ActiveEffects>, mut config: ResMut<RapierConfiguration>) {
-	if active_effects.has_effect(EffectType::NoGravity).is_some() {
-		config.gravity = Vec3::splat(0.0);
-	} else if let Some(gravity) = active_effects.has_effect(EffectType::LowGravity) {
-		config.gravity = Vec3::Y * lerp(-6.0..=-2.0, gravity.potency);
-	} else {
-		config.gravity = Vec3::Y * -9.8;
-	}
+fn low_gravity(active_effects: Res<ActiveEffects>, mut config: ResMut<RapierConfiguration>) {
+	let Some(gravity) = active_effects.has_effect(EffectType::LOW_GRAVITY) else { return; };
+
+	config.gravity = Vec3::Y * lerp(-6.0..=-2.0, gravity.potency);
 }
 
-#[derive(Debug, Clone, Copy, Reflect, FromReflect, PartialEq, Eq, Hash)]
-pub enum EffectType {
-	Haste,            // DONE
-	Slowness,         // DONE
-	Arrow,            // DONE
-	Earthquake,       // DONE
-	SpawnBall,        // WIP
-	BackpackBackflip, //WIP
-	SmallInstruments, //WIP
-	InvisibleIngridients,
-	TreeRockets,
-	NoGravity,  // DONE
-	LowGravity, // DONE
-	WhackyGravity,
-	LuckyHands,
-	Hallucinations, // DONE
-	Thief,
-	RareArrows, // DONE
-	GodMode,
+fn whacky_gravity(
+	active_effects: Res<ActiveEffects>,
+	mut config: ResMut<RapierConfiguration>,
+	time: Res<Time>,
+) {
+	let Some(gravity) = active_effects.has_effect(EffectType::WHACKY_GRAVITY) else { return; };
+
+	// Wobbles around, stronger potions tip it further sideways
+	let t = time.elapsed_seconds();
+	let tilt = lerp(0.6..=1.4, gravity.potency);
+	let rotation = Quat::from_euler(
+		EulerRot::XYZ,
+		(t * 0.5).sin() * tilt,
+		0.0,
+		(t * 0.37 + 1.0).cos() * tilt,
+	);
+
+	config.gravity = rotation * Vec3::Y * -9.8;
+}
+
+/// Another gravity effect might still be active, its tick runs right after
+fn reset_gravity(mut config: ResMut<RapierConfiguration>) {
+	config.gravity = Vec3::Y * -9.8;
 }
 
 pub fn earthquake(
@@ -206,7 +564,7 @@ pub fn earthquake(
 	time: Res<Time>,
 	mut last_boom: Local<f32>,
 ) {
-	if let Some(earthquake) = active_effects.has_effect(EffectType::Earthquake) {
+	if let Some(earthquake) = active_effects.has_effect(EffectType::EARTHQUAKE) {
 		if time.elapsed_seconds() > *last_boom {
 			let mut rng = thread_rng();
 			*last_boom = time.elapsed_seconds() + rng.gen_range(0.5..3.0);
@@ -226,6 +584,252 @@ pub fn earthquake(
 	}
 }
 
+/// Cleans up the balls, that rolled off the island
+fn despawn_fallen_balls(
+	mut commands: Commands,
+	ball_query: Query<(Entity, &Transform), With<Ball>>,
+) {
+	for (entity, transform) in &ball_query {
+		if transform.translation.y < -30.0 {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+}
+
+/// Rains balls around the player
+fn spawn_balls(
+	mut commands: Commands,
+	ball_assets: Res<BallAssets>,
+	player_query: Query<&Transform, With<Player>>,
+	ball_query: Query<(), With<Ball>>,
+	active_effects: Res<ActiveEffects>,
+	time: Res<Time>,
+	mut next_ball: Local<f32>,
+) {
+	const MAX_BALLS: usize = 40;
+
+	let Some(balls) = active_effects.has_effect(EffectType::SPAWN_BALL) else { return; };
+	let Ok(player_transform) = player_query.get_single() else { return; };
+
+	if time.elapsed_seconds() < *next_ball || ball_query.iter().len() >= MAX_BALLS {
+		return;
+	}
+
+	let mut rng = thread_rng();
+	*next_ball = time.elapsed_seconds() + lerp(3.0..=0.8, balls.potency) * rng.gen_range(0.5..1.5);
+
+	let offset = Vec3::new(rng.gen_range(-4.0..4.0), 6.0, rng.gen_range(-4.0..4.0));
+	spawn_ball(
+		&mut commands,
+		&ball_assets,
+		player_transform.translation + offset,
+	);
+}
+
+/// How long one backflip takes
+const BACKFLIP_TIME: f32 = 1.2;
+
+/// Backflips of the backpack, scheduled by `EffectType::BACKPACK_BACKFLIP`
+#[derive(Component, Debug, Default)]
+pub struct Backflip {
+	next_flip: f32,
+	flip_start: Option<f32>,
+}
+
+/// First flip comes shortly after drinking
+fn start_backflips(
+	mut commands: Commands,
+	backpack_query: Query<Entity, With<Backpack>>,
+	time: Res<Time>,
+) {
+	let Ok(backpack) = backpack_query.get_single() else { return; };
+
+	commands.entity(backpack).insert(Backflip {
+		next_flip: time.elapsed_seconds() + 2.0,
+		flip_start: None,
+	});
+}
+
+/// Every now and then flips the backpack over, spilling its contents
+fn backpack_backflip(
+	mut backpack_query: Query<&mut Backflip>,
+	active_effects: Res<ActiveEffects>,
+	time: Res<Time>,
+) {
+	let Ok(mut backflip) = backpack_query.get_single_mut() else { return; };
+	let Some(effect) = active_effects.has_effect(EffectType::BACKPACK_BACKFLIP) else { return; };
+
+	if backflip.flip_start.is_none() && time.elapsed_seconds() > backflip.next_flip {
+		backflip.flip_start = Some(time.elapsed_seconds());
+		backflip.next_flip = time.elapsed_seconds() + lerp(12.0..=5.0, effect.potency);
+	}
+}
+
+/// Keeps going after the effect ends, so the backpack always lands
+fn animate_backflip(mut backpack_query: Query<(&mut Transform, &mut Backflip)>, time: Res<Time>) {
+	let Ok((mut transform, mut backflip)) = backpack_query.get_single_mut() else { return; };
+	let Some(start) = backflip.flip_start else { return; };
+
+	let t = ((time.elapsed_seconds() - start) / BACKFLIP_TIME).min(1.0);
+	// Ease in-out, so it's slow while upside down
+	let eased = t * t * (3.0 - 2.0 * t);
+
+	// Flip around the middle of the backpack, not its bottom
+	let pivot = BACKPACK_TRANSFORM.translation + Vec3::Y * BACKPACK_TRANSFORM.scale.y * 0.3;
+	*transform = BACKPACK_TRANSFORM;
+	transform.rotate_around(pivot, Quat::from_rotation_z(std::f32::consts::TAU * eased));
+
+	if t >= 1.0 {
+		*transform = BACKPACK_TRANSFORM;
+		backflip.flip_start = None;
+	}
+}
+
+/// Shrinks alchemy tools, so it's harder to brew
+fn small_instruments(
+	mut item_query: Query<(&Item, &mut ItemSize)>,
+	active_effects: Res<ActiveEffects>,
+) {
+	let Some(small) = active_effects.has_effect(EffectType::SMALL_INSTRUMENTS) else { return; };
+
+	set_instrument_size(&mut item_query, lerp(0.7..=0.4, small.potency));
+}
+
+fn normal_instruments(mut item_query: Query<(&Item, &mut ItemSize)>) {
+	set_instrument_size(&mut item_query, 1.0);
+}
+
+fn set_instrument_size(item_query: &mut Query<(&Item, &mut ItemSize)>, mult: f32) {
+	for (item, mut item_size) in item_query {
+		if matches!(item, Item::AlchemyTool) && item_size.effect_mult != mult {
+			item_size.effect_mult = mult;
+		}
+	}
+}
+
+/// Ingredient hidden by `EffectType::INVISIBLE_INGRIDIENTS`
+#[derive(Component)]
+pub struct Invisible;
+
+/// Hides ingredients growing in the world. They can still be picked up, and shimmer when close.
+fn invisible_ingredients(
+	mut commands: Commands,
+	player_query: Query<&Transform, With<Player>>,
+	mut ingredient_query: Query<
+		(Entity, &Transform, &mut Visibility, Option<&Invisible>),
+		(
+			With<Ingredient>,
+			With<SpawnableInstance>,
+			Without<DroppedItem>,
+		),
+	>,
+	mut invisible_query: Query<(Entity, &mut Visibility), (With<Invisible>, Without<Ingredient>)>,
+	active_effects: Res<ActiveEffects>,
+	time: Res<Time>,
+) {
+	let Some(invisible) = active_effects.has_effect(EffectType::INVISIBLE_INGRIDIENTS) else { return; };
+
+	// Harvested ones are not ingredients anymore, so what's left should be seen
+	for (entity, mut visibility) in &mut invisible_query {
+		*visibility = Visibility::Inherited;
+		commands.entity(entity).remove::<Invisible>();
+	}
+
+	let Ok(player_transform) = player_query.get_single() else { return; };
+	let shimmer_distance = lerp(4.0..=2.0, invisible.potency);
+
+	for (entity, transform, mut visibility, hidden) in &mut ingredient_query {
+		let distance = transform.translation.distance(player_transform.translation);
+
+		// Every ingredient shimmers at its own pace
+		let phase = transform.translation.x * 1.7 + transform.translation.z * 2.3;
+		let shimmer =
+			distance < shimmer_distance && (time.elapsed_seconds() * 12.0 + phase).sin() > 0.3;
+
+		let desired = if shimmer {
+			Visibility::Inherited
+		} else {
+			Visibility::Hidden
+		};
+
+		if *visibility != desired {
+			*visibility = desired;
+		}
+		if hidden.is_none() {
+			commands.entity(entity).insert(Invisible);
+		}
+	}
+}
+
+/// Shows everything again
+fn reveal_ingredients(
+	mut commands: Commands,
+	mut invisible_query: Query<(Entity, &mut Visibility), With<Invisible>>,
+) {
+	for (entity, mut visibility) in &mut invisible_query {
+		*visibility = Visibility::Inherited;
+		commands.entity(entity).remove::<Invisible>();
+	}
+}
+
+/// Tree launched by `EffectType::TREE_ROCKETS`, remembers where it came from
+#[derive(Component)]
+pub struct TreeRocket {
+	pub origin: Transform,
+}
+
+/// Launches trees near the player into the sky
+fn tree_rockets(
+	mut commands: Commands,
+	player_query: Query<&Transform, With<Player>>,
+	tree_query: Query<(Entity, &Transform, &SpawnableInstance), Without<TreeRocket>>,
+	spawnables: Res<Assets<Spawnable>>,
+	active_effects: Res<ActiveEffects>,
+) {
+	let Some(rockets) = active_effects.has_effect(EffectType::TREE_ROCKETS) else { return; };
+
+	let Ok(player_transform) = player_query.get_single() else { return; };
+	let launch_distance = lerp(8.0..=16.0, rockets.potency);
+
+	let mut rng = thread_rng();
+
+	for (entity, transform, instance) in &tree_query {
+		if transform.translation.distance(player_transform.translation) > launch_distance {
+			continue;
+		}
+
+		let Some(spawnable) = spawnables.get(&instance.handle) else { continue; };
+		if spawnable.archetype != SpawnableArchetype::Tree {
+			continue;
+		}
+
+		commands.entity(entity).insert((
+			TreeRocket { origin: *transform },
+			RigidBody::Dynamic,
+			Velocity {
+				linvel: Vec3::Y * rng.gen_range(8.0..14.0),
+				angvel: Vec3::new(rng.gen_range(-1.0..1.0), 0.0, rng.gen_range(-1.0..1.0)),
+			},
+			// Keeps on flying up
+			GravityScale(-0.5),
+		));
+	}
+}
+
+/// Puts the trees back where they were
+fn land_tree_rockets(
+	mut commands: Commands,
+	mut rocket_query: Query<(Entity, &mut Transform, &TreeRocket)>,
+) {
+	for (entity, mut transform, rocket) in &mut rocket_query {
+		*transform = rocket.origin;
+		commands
+			.entity(entity)
+			.insert(RigidBody::Fixed)
+			.remove::<(TreeRocket, Velocity, GravityScale)>();
+	}
+}
+
 /// Effects and time left for them to wear off
 #[derive(Clone, Copy, Debug, Reflect, FromReflect)]
 pub struct Effect {
@@ -234,11 +838,72 @@ pub struct Effect {
 	pub time_left: f32,
 }
 
-/// Added to the player to determine active effects
+/// How an effect combines with the already active one of the same type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackRule {
+	/// Potencies add up, lasts as long as the longer one
+	StackPotency,
+	/// Keeps the stronger potency, duration starts over
+	Refresh,
+	/// New effect takes the place of the old one, or of the active one in its group
+	Replace,
+	/// Weakens (or removes) the active opposite effect first, then refreshes
+	CancelOpposite,
+}
+
+/// Added to the player to determine active effects.
+/// There is at most one effect of each type, use `push` to add new ones.
 #[derive(Resource, Clone, Debug, Default)]
 pub struct ActiveEffects(pub SmallVec<[Effect; 12]>);
 
 impl ActiveEffects {
+	/// Applies the effect, following its `StackRule`
+	pub fn push(&mut self, mut effect: Effect, registry: &EffectRegistry) {
+		let rule = registry.stack_rule(effect.effect);
+
+		if rule == StackRule::CancelOpposite {
+			if let Some(opposite) = registry
+				.opposite(effect.effect)
+				.and_then(|o| self.0.iter().position(|e| e.effect == o))
+			{
+				let cancelled = self.0[opposite].potency.min(effect.potency);
+
+				self.0[opposite].potency -= cancelled;
+				effect.potency -= cancelled;
+
+				if self.0[opposite].potency <= 0.0 {
+					self.0.remove(opposite);
+				}
+				if effect.potency <= 0.0 {
+					return;
+				}
+			}
+		}
+
+		let group = registry.group(effect.effect);
+		let Some(active) = self.0.iter_mut().find(|e| {
+			e.effect == effect.effect
+				|| (rule == StackRule::Replace
+					&& group.is_some()
+					&& registry.group(e.effect) == group)
+		}) else {
+			self.0.push(effect);
+			return;
+		};
+
+		match rule {
+			StackRule::StackPotency => {
+				active.potency = (active.potency + effect.potency).min(1.0);
+				active.time_left = active.time_left.max(effect.time_left);
+			}
+			StackRule::Refresh | StackRule::CancelOpposite => {
+				active.potency = active.potency.max(effect.potency);
+				active.time_left = effect.time_left;
+			}
+			StackRule::Replace => *active = effect,
+		}
+	}
+
 	pub fn has_effect(&self, effect_type: EffectType) -> Option<Effect> {
 		self.0
 			.iter()
@@ -248,7 +913,15 @@ impl ActiveEffects {
 	}
 }
 
-pub fn generate_qp_from_ingredients(ingridients: &[Ingredient]) -> (f32, f32) {
+/// How far off (relative to the ideal time) simmering can be, and still be perfect
+pub const SIMMER_TOLERANCE: f32 = 0.15;
+
+/// Seconds the ingredients should simmer in a heated cauldron for the best brew
+pub fn ideal_simmer_time(ingridients: &[Ingredient]) -> f32 {
+	5.0 + ingridients.len() as f32 * 2.0
+}
+
+pub fn generate_qp_from_ingredients(ingridients: &[Ingredient], simmer_time: f32) -> (f32, f32) {
 	let mut quality: f32 = 0.5;
 	let mut potency: f32 = 0.5;
 
@@ -261,70 +934,92 @@ pub fn generate_qp_from_ingredients(ingridients: &[Ingredient]) -> (f32, f32) {
 		5..=6 => 0.3,
 		7 => 0.1,
 		8.. => (8 - len) as f32 * 0.1,
-		_ => 0.0,
 	};
-	potency *= ingridients.iter().fold(1.0, |acc, c| acc * c.size);
+	potency *= ingridients
+		.iter()
+		.fold(1.0, |acc, c| acc * c.size * c.grind.potency_mult());
 
 	quality -= len as f32 * 0.05;
 	quality += ingridients.iter().filter(|c| c.is_rare).count() as f32 * 0.20;
 	quality += ingridients
 		.iter()
-		.filter(|c| c.grind == Grind::Grinded)
-		.count() as f32
-		* 0.10;
+		.map(|c| c.grind.quality_bonus())
+		.sum::<f32>();
+
+	// Brew pulled out just in time gets a bonus, undercooked and overcooked ones get worse
+	let timing = (simmer_time - ideal_simmer_time(ingridients)) / ideal_simmer_time(ingridients);
+	quality += match timing {
+		t if t.abs() <= SIMMER_TOLERANCE => 0.2,
+		t if t < 0.0 => t * 0.2,
+		t => -t.min(2.0) * 0.2,
+	};
 
 	(quality.clamp(0.1, 1.0), potency.clamp(0.1, 1.0))
 }
 
-// Potion quality and potency goes from 0.0 to 1.0
-pub fn generate_effects_from_qp(quality: f32, potency: f32) -> Vec<Effect> {
+/// Brews effects out of the ingredients.
+/// Same ingredients always give the same effects, quality and potency only scale them.
+pub fn generate_effects_from_ingredients(
+	ingridients: &[Ingredient],
+	quality: f32,
+	potency: f32,
+	registry: &EffectRegistry,
+) -> Vec<Effect> {
 	debug_assert!((0.0..=1.0).contains(&quality) || (0.0..=1.0).contains(&potency));
 
 	if quality == 0.0 {
 		return vec![];
 	}
 
-	let mut rng = thread_rng();
-
-	let mut effect_quality = vec![];
+	// Effect type, strength (how many times it was added) and summed duration
+	let mut brew: SmallVec<[(EffectType, f32, f32); 8]> = smallvec![];
 
-	for _i in 0..(potency / 0.45).ceil() as usize {
-		let roll = rng.gen::<f32>();
+	// Grinding decides how much of the ingredient gets extracted
+	for (effect, duration_mult) in ingridients.iter().flat_map(|i| {
+		i.effects
+			.iter()
+			.take(i.grind.extracted_effects())
+			.map(|e| (e, i.grind.duration_mult()))
+	}) {
+		let duration = effect.duration * duration_mult;
 
-		let weighted_roll = if quality < 0.5 {
-			lerp(roll..=0.0, (0.5 - quality) * 2.0)
+		if let Some(brewed) = brew.iter_mut().find(|b| b.0 == effect.effect_type) {
+			brewed.1 += 1.0;
+			brewed.2 += duration;
 		} else {
-			lerp(roll..=1.0, (quality - 0.5) * 2.0)
-		};
-
-		effect_quality.push(match weighted_roll {
-			x if x <= 0.1 => EffectQuality::Catastrophic,
-			x if x > 0.1 && x <= 0.4 => EffectQuality::Negative,
-			x if x > 0.4 && x <= 0.6 => EffectQuality::Neutral,
-			x if x > 0.6 && x <= 0.9 => EffectQuality::Positive,
-			x if x > 0.9 => EffectQuality::Exceptional,
-			_ => panic!(),
-		});
+			brew.push((effect.effect_type, 1.0, duration));
+		}
 	}
 
-	let roll = rng.gen::<f32>();
+	// Conflicting effects cancel each other out
+	for i in 0..brew.len() {
+		let Some(opposite) = registry.opposite(brew[i].0) else { continue; };
+		let Some(j) = brew.iter().position(|b| b.0 == opposite) else { continue; };
 
-	let potency = if quality < 0.5 {
-		lerp(roll..=0.0, (0.5 - quality) * 2.0)
-	} else {
-		lerp(roll..=1.0, (quality - 0.5) * 2.0)
-	};
+		let cancelled = brew[i].1.min(brew[j].1);
+		if cancelled <= 0.0 {
+			continue;
+		}
 
-	effect_quality
-		.iter()
-		.map(|i| Effect {
-			effect: generate_effect(i, &mut rng),
-			potency,
-			time_left: (rng.gen_range(30.0..120.0) * potency).max(10.0),
+		for k in [i, j] {
+			let (_, strength, duration) = &mut brew[k];
+			*duration *= (*strength - cancelled) / *strength;
+			*strength -= cancelled;
+		}
+	}
+
+	brew.iter()
+		.filter(|(_, strength, _)| *strength > 0.0)
+		.map(|(effect, strength, duration)| Effect {
+			effect: *effect,
+			// Every ingredient sharing the effect makes it stronger
+			potency: (potency * lerp(0.5..=1.0, ((strength - 1.0) / 3.0).min(1.0))).clamp(0.1, 1.0),
+			time_left: (duration / strength * lerp(0.5..=1.5, quality)).max(10.0),
 		})
 		.collect()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EffectQuality {
 	Catastrophic,
 	Negative,
@@ -333,41 +1028,355 @@ pub enum EffectQuality {
 	Exceptional,
 }
 
-pub fn generate_effect(effect_quality: &EffectQuality, rng: &mut impl Rng) -> EffectType {
-	use EffectType::*;
+impl EffectQuality {
+	pub const ALL: [EffectQuality; 5] = [
+		EffectQuality::Catastrophic,
+		EffectQuality::Negative,
+		EffectQuality::Neutral,
+		EffectQuality::Positive,
+		EffectQuality::Exceptional,
+	];
+
+	/// Roll goes from 0.0 (worst) to 1.0 (best)
+	pub fn from_roll(roll: f32) -> Self {
+		match roll {
+			x if x <= 0.1 => EffectQuality::Catastrophic,
+			x if x > 0.1 && x <= 0.4 => EffectQuality::Negative,
+			x if x > 0.4 && x <= 0.6 => EffectQuality::Neutral,
+			x if x > 0.6 && x <= 0.9 => EffectQuality::Positive,
+			x if x > 0.9 => EffectQuality::Exceptional,
+			_ => panic!(),
+		}
+	}
 
-	let choice = match effect_quality {
-		EffectQuality::Catastrophic => {
-			const CATASTROPHIC: Choices<EffectType> = choice!(Earthquake,);
-			CATASTROPHIC
+	/// Frame around the effect swatch in the HUD
+	pub fn color(&self) -> Color {
+		match self {
+			EffectQuality::Catastrophic => Color::rgb(0.6, 0.0, 0.0),
+			EffectQuality::Negative => Color::rgb(0.8, 0.4, 0.2),
+			EffectQuality::Neutral => Color::GRAY,
+			EffectQuality::Positive => Color::rgb(0.3, 0.8, 0.3),
+			EffectQuality::Exceptional => Color::GOLD,
 		}
-		EffectQuality::Negative => {
-			const NEGATIVE: Choices<EffectType> = choice!(Slowness, Hallucinations,);
-			NEGATIVE
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use bevy::{time::TimeUpdateStrategy, utils::Instant};
+
+	use super::*;
+
+	/// Headless game with only the effects
+	fn effects_app() -> App {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.add_plugin(AssetPlugin::default())
+			.add_asset::<Spawnable>()
+			.add_asset::<Mesh>()
+			.add_asset::<StandardMaterial>()
+			.init_resource::<RapierConfiguration>()
+			.add_state::<GameState>()
+			.add_plugin(EffectsPlugin);
+
+		// Skips OnEnter, the arrows need game assets
+		app.update();
+		app.world.insert_resource(State(GameState::InGame));
+
+		app.world.spawn((Player, Transform::default()));
+		app
+	}
+
+	/// Runs one frame, that takes a tenth of a second
+	fn step(app: &mut App) {
+		let last_update = app.world.resource::<Time>().last_update();
+		let now = last_update.unwrap_or_else(Instant::now) + Duration::from_secs_f32(0.1);
+
+		app.insert_resource(TimeUpdateStrategy::ManualInstant(now));
+		app.update();
+	}
+
+	fn push_effect(app: &mut App, effect: EffectType, time_left: f32) {
+		let registry = app.world.resource::<EffectRegistry>().clone();
+		app.world.resource_mut::<ActiveEffects>().push(
+			Effect {
+				effect,
+				potency: 0.5,
+				time_left,
+			},
+			&registry,
+		);
+	}
+
+	fn run_until_expired(app: &mut App, effect: EffectType) {
+		for _ in 0..100 {
+			step(app);
+			if app
+				.world
+				.resource::<ActiveEffects>()
+				.has_effect(effect)
+				.is_none()
+			{
+				// Expire systems run in the frame after
+				step(app);
+				return;
+			}
 		}
-		EffectQuality::Neutral => {
-			const NEUTRAL: Choices<EffectType> = choice!(
-				//SpawnBall,
-				//TreeRockets,
-				NoGravity,
+		panic!("{effect:?} never expired");
+	}
+
+	#[test]
+	fn invisible_ingredients_show_up_again() {
+		let mut app = effects_app();
+
+		let ingredients: Vec<Entity> = [Vec3::X * 10.0, Vec3::Z * -20.0, Vec3::new(30.0, 0.0, 5.0)]
+			.into_iter()
+			.map(|position| {
+				app.world
+					.spawn((
+						Ingredient::default(),
+						SpawnableInstance {
+							handle: Handle::default(),
+							size: 1.0,
+							rare: false,
+						},
+						Transform::from_translation(position),
+						Visibility::Inherited,
+					))
+					.id()
+			})
+			.collect();
+
+		push_effect(&mut app, EffectType::INVISIBLE_INGRIDIENTS, 1.0);
+		step(&mut app);
+		step(&mut app);
+
+		for &entity in &ingredients {
+			assert_eq!(
+				app.world.get::<Visibility>(entity),
+				Some(&Visibility::Hidden)
 			);
-			NEUTRAL
+			assert!(app.world.get::<Invisible>(entity).is_some());
+		}
+
+		// Already hidden ones are left alone
+		let last_frame = app.world.read_change_tick();
+		step(&mut app);
+		for &entity in &ingredients {
+			let ticks = app
+				.world
+				.entity(entity)
+				.get_change_ticks::<Invisible>()
+				.unwrap();
+			assert!(!ticks.is_changed(last_frame, app.world.read_change_tick()));
 		}
-		EffectQuality::Positive => {
-			const POSITIVE: Choices<EffectType> = choice!(
-				Haste, Arrow,
-				//LuckyHands,
+
+		run_until_expired(&mut app, EffectType::INVISIBLE_INGRIDIENTS);
+
+		for &entity in &ingredients {
+			assert_eq!(
+				app.world.get::<Visibility>(entity),
+				Some(&Visibility::Inherited)
 			);
-			POSITIVE
+			assert!(app.world.get::<Invisible>(entity).is_none());
 		}
-		EffectQuality::Exceptional => {
-			const EXCEPTIONAL: Choices<EffectType> = choice!(
-				//GodMode,
-				RareArrows,
+	}
+
+	#[test]
+	fn tree_rockets_land_where_they_started() {
+		let mut app = effects_app();
+
+		let mut spawnables = app.world.resource_mut::<Assets<Spawnable>>();
+		let mut spawnable = |archetype| {
+			spawnables.add(Spawnable {
+				id: 0,
+				archetype,
+				scene: Handle::default(),
+				ingredient: None,
+				spawn_weight: 1.0,
+				size: 1.0,
+				collider: None,
+			})
+		};
+		let tree = spawnable(SpawnableArchetype::Tree);
+		let bush = spawnable(SpawnableArchetype::Bush);
+
+		let spawned: Vec<(Entity, Transform)> = [
+			(&tree, Vec3::new(3.0, 0.0, 1.0)),
+			(&tree, Vec3::new(-2.0, 0.0, -4.0)),
+			(&bush, Vec3::new(1.0, 0.0, 2.0)),
+		]
+		.into_iter()
+		.map(|(handle, position)| {
+			let transform = Transform::from_translation(position)
+				.with_rotation(Quat::from_rotation_y(position.x))
+				.with_scale(Vec3::splat(1.2));
+			let entity = app
+				.world
+				.spawn((
+					SpawnableInstance {
+						handle: handle.clone(),
+						size: 1.0,
+						rare: false,
+					},
+					transform,
+				))
+				.id();
+			(entity, transform)
+		})
+		.collect();
+
+		push_effect(&mut app, EffectType::TREE_ROCKETS, 1.0);
+		step(&mut app);
+		step(&mut app);
+
+		let (trees, bushes) = spawned.split_at(2);
+		for &(entity, _) in trees {
+			assert!(app.world.get::<TreeRocket>(entity).is_some());
+		}
+		for &(entity, _) in bushes {
+			assert!(app.world.get::<TreeRocket>(entity).is_none());
+		}
+
+		// No physics here, so fly them off by hand
+		for &(entity, _) in trees {
+			let mut transform = app.world.get_mut::<Transform>(entity).unwrap();
+			transform.translation.y += 40.0;
+			transform.rotate_x(1.0);
+		}
+
+		run_until_expired(&mut app, EffectType::TREE_ROCKETS);
+
+		for &(entity, transform) in &spawned {
+			assert_eq!(app.world.get::<Transform>(entity), Some(&transform));
+			assert!(app.world.get::<TreeRocket>(entity).is_none());
+		}
+	}
+
+	/// One effect for every stack rule
+	fn stacking_registry() -> EffectRegistry {
+		let def = |name, stack_rule| EffectDef {
+			stack_rule,
+			..EffectDef::new(EffectType(name), EffectQuality::Neutral)
+		};
+
+		EffectRegistry(vec![
+			def("Refresh", StackRule::Refresh),
+			def("StackPotency", StackRule::StackPotency),
+			def("Replace", StackRule::Replace),
+			EffectDef {
+				group: Some("Group"),
+				..def("ReplaceA", StackRule::Replace)
+			},
+			EffectDef {
+				group: Some("Group"),
+				..def("ReplaceB", StackRule::Replace)
+			},
+			EffectDef {
+				opposite: Some(EffectType("Opposite")),
+				..def("Cancel", StackRule::CancelOpposite)
+			},
+			EffectDef {
+				opposite: Some(EffectType("Cancel")),
+				..def("Opposite", StackRule::CancelOpposite)
+			},
+		])
+	}
+
+	/// Pushes both effects, and returns what's active
+	fn stack(first: (&'static str, f32, f32), second: (&'static str, f32, f32)) -> ActiveEffects {
+		let registry = stacking_registry();
+		let mut active_effects = ActiveEffects::default();
+
+		for (name, potency, time_left) in [first, second] {
+			active_effects.push(
+				Effect {
+					effect: EffectType(name),
+					potency,
+					time_left,
+				},
+				&registry,
 			);
-			EXCEPTIONAL
 		}
-	};
+		active_effects
+	}
+
+	fn potency_and_time(active_effects: &ActiveEffects) -> Vec<(&'static str, f32, f32)> {
+		active_effects
+			.0
+			.iter()
+			.map(|e| (e.effect.0, e.potency, e.time_left))
+			.collect()
+	}
+
+	#[test]
+	fn refresh_keeps_stronger_potency_and_restarts() {
+		let active_effects = stack(("Refresh", 0.75, 30.0), ("Refresh", 0.5, 10.0));
+		assert_eq!(potency_and_time(&active_effects), [("Refresh", 0.75, 10.0)]);
+	}
+
+	#[test]
+	fn stack_potency_adds_up() {
+		let active_effects = stack(("StackPotency", 0.25, 30.0), ("StackPotency", 0.5, 10.0));
+		assert_eq!(
+			potency_and_time(&active_effects),
+			[("StackPotency", 0.75, 30.0)]
+		);
+
+		let active_effects = stack(("StackPotency", 0.75, 10.0), ("StackPotency", 0.5, 30.0));
+		assert_eq!(
+			potency_and_time(&active_effects),
+			[("StackPotency", 1.0, 30.0)]
+		);
+	}
 
-	*choice.random(rng)
+	#[test]
+	fn replace_takes_the_place_of_the_old_one() {
+		let active_effects = stack(("Replace", 0.75, 30.0), ("Replace", 0.25, 10.0));
+		assert_eq!(potency_and_time(&active_effects), [("Replace", 0.25, 10.0)]);
+
+		let active_effects = stack(("ReplaceA", 0.75, 30.0), ("ReplaceB", 0.25, 10.0));
+		assert_eq!(
+			potency_and_time(&active_effects),
+			[("ReplaceB", 0.25, 10.0)]
+		);
+
+		// Group doesn't reach effects outside of it
+		let active_effects = stack(("Replace", 0.75, 30.0), ("ReplaceA", 0.25, 10.0));
+		assert_eq!(
+			potency_and_time(&active_effects),
+			[("Replace", 0.75, 30.0), ("ReplaceA", 0.25, 10.0)]
+		);
+	}
+
+	#[test]
+	fn cancel_opposite_weakens_both() {
+		let active_effects = stack(("Cancel", 0.75, 30.0), ("Opposite", 0.25, 10.0));
+		assert_eq!(potency_and_time(&active_effects), [("Cancel", 0.5, 30.0)]);
+
+		let active_effects = stack(("Cancel", 0.25, 30.0), ("Opposite", 0.75, 10.0));
+		assert_eq!(potency_and_time(&active_effects), [("Opposite", 0.5, 10.0)]);
+
+		// Without the opposite, it refreshes
+		let active_effects = stack(("Cancel", 0.25, 30.0), ("Cancel", 0.75, 10.0));
+		assert_eq!(potency_and_time(&active_effects), [("Cancel", 0.75, 10.0)]);
+	}
+
+	#[test]
+	fn only_one_gravity_at_a_time() {
+		let mut app = effects_app();
+
+		push_effect(&mut app, EffectType::NO_GRAVITY, 30.0);
+		push_effect(&mut app, EffectType::WHACKY_GRAVITY, 30.0);
+		push_effect(&mut app, EffectType::LOW_GRAVITY, 30.0);
+
+		let active_effects = app.world.resource::<ActiveEffects>();
+		assert!(active_effects.has_effect(EffectType::LOW_GRAVITY).is_some());
+		assert!(active_effects.has_effect(EffectType::NO_GRAVITY).is_none());
+		assert!(active_effects
+			.has_effect(EffectType::WHACKY_GRAVITY)
+			.is_none());
+	}
 }
@@ -0,0 +1,180 @@
+use bevy_inspector_egui::egui::lerp;
+
+use crate::{assets::Spawnable, prelude::*};
+
+use super::{
+	effects::{
+		ActiveEffects, EffectAppExt, EffectDef, EffectQuality, EffectStage, EffectType, StackRule,
+	},
+	ingredient::{Ingredient, SpeciesRegistry},
+	player::Player,
+	world::SpawnableInstance,
+};
+
+pub struct HallucinationsPlugin;
+impl Plugin for HallucinationsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_effect(EffectDef {
+			stack_rule: StackRule::CancelOpposite,
+			opposite: Some(EffectType::RARE_ARROWS),
+			color: Color::rgb(0.9, 0.2, 0.9),
+			..EffectDef::new(EffectType::HALLUCINATIONS, EffectQuality::Negative)
+		})
+		.add_effect_system(
+			EffectType::HALLUCINATIONS,
+			EffectStage::Tick,
+			spawn_phantoms,
+		)
+		.add_effect_system(
+			EffectType::HALLUCINATIONS,
+			EffectStage::Expire,
+			dissolve_phantoms,
+		)
+		.add_systems((wobble_phantoms, dissolve).in_set(OnUpdate(GameState::InGame)));
+	}
+}
+
+impl EffectType {
+	pub const HALLUCINATIONS: EffectType = EffectType("Hallucinations");
+}
+
+/// Ingredient, that isn't really there. Looks like the real thing, but with wrong colors.
+#[derive(Component, Debug, Default)]
+pub struct Phantom {
+	age: f32,
+	size: f32,
+}
+
+/// Shrinks away and despawns, used for things that turn out to be hallucinations
+#[derive(Component, Debug, Default)]
+pub struct Dissolving {
+	age: f32,
+	start_scale: Option<Vec3>,
+}
+
+/// How long dissolving takes
+const DISSOLVE_TIME: f32 = 0.6;
+
+fn spawn_phantoms(
+	mut commands: Commands,
+	player_query: Query<&Transform, With<Player>>,
+	phantom_query: Query<(), (With<Phantom>, Without<Dissolving>)>,
+	spawnable_assets: Res<Assets<Spawnable>>,
+	species_registry: Res<SpeciesRegistry>,
+	active_effects: Res<ActiveEffects>,
+	time: Res<Time>,
+	mut next_phantom: Local<f32>,
+) {
+	let Some(hallucination) = active_effects.has_effect(EffectType::HALLUCINATIONS) else { return; };
+	let Ok(player_transform) = player_query.get_single() else { return; };
+
+	let max_phantoms = lerp(3.0..=8.0, hallucination.potency) as usize;
+	if time.elapsed_seconds() < *next_phantom || phantom_query.iter().len() >= max_phantoms {
+		return;
+	}
+
+	let mut rng = thread_rng();
+	*next_phantom =
+		time.elapsed_seconds() + lerp(4.0..=1.5, hallucination.potency) * rng.gen_range(0.5..1.5);
+
+	let Some((handle_id, spawnable)) = spawnable_assets
+		.iter()
+		.filter(|(_, s)| s.ingredient.is_some())
+		.choose(&mut rng)
+	else {
+		return;
+	};
+	let Some((species_id, species)) =
+		species_registry.random_for_spawnable((spawnable.archetype, spawnable.id), &mut rng)
+	else {
+		return;
+	};
+
+	// Same species, but the colors are all wrong
+	let hue = (rng.gen_range(species.hue.clone()) + rng.gen_range(90.0..270.0)).rem_euclid(360.0);
+	let is_rare = rng.gen_bool(lerp(0.1..=0.3, hallucination.potency as f64));
+	let (materials, color) = NamedMaterials::generate_materials(
+		spawnable.archetype,
+		is_rare,
+		Some(hue..hue + 1.0),
+		&mut rng,
+	);
+
+	let size = rng.gen_range(0.7..1.35) * species.base_size;
+	let offset = Quat::from_rotation_y(rng.gen_range(-PI..PI)) * Vec3::Z * rng.gen_range(4.0..10.0);
+
+	let mut handle = Handle::<Spawnable>::weak(handle_id);
+	handle.make_strong(&spawnable_assets);
+
+	// No collider, the player walks right through them
+	commands.spawn((
+		Name::new("Phantom"),
+		Phantom { age: 0.0, size },
+		SpawnableInstance {
+			handle,
+			rare: is_rare,
+			size,
+		},
+		Ingredient::from_species(species_id, species, is_rare, color, size),
+		SceneBundle {
+			scene: spawnable.scene.clone(),
+			transform: Transform::from_translation(
+				(player_transform.translation + offset) * Vec3::new(1.0, 0.0, 1.0),
+			)
+			.with_scale(Vec3::ZERO)
+			.with_rotation(Quat::from_rotation_y(rng.gen_range(-PI..PI))),
+			..default()
+		},
+		materials,
+	));
+}
+
+/// Phantoms fade in, and never quite hold still
+fn wobble_phantoms(
+	mut phantom_query: Query<(&mut Transform, &mut Phantom), Without<Dissolving>>,
+	time: Res<Time>,
+) {
+	for (mut transform, mut phantom) in &mut phantom_query {
+		phantom.age += time.delta_seconds();
+
+		let appear = (phantom.age / DISSOLVE_TIME).min(1.0);
+		let wobble = Vec3::new(
+			(phantom.age * 3.1).sin(),
+			(phantom.age * 2.3).cos(),
+			(phantom.age * 2.7).sin(),
+		) * 0.08;
+
+		transform.scale = (Vec3::ONE + wobble) * phantom.size * appear;
+	}
+}
+
+/// The effect is over, and the phantoms go away
+fn dissolve_phantoms(
+	mut commands: Commands,
+	phantom_query: Query<Entity, (With<Phantom>, Without<Dissolving>)>,
+) {
+	for entity in &phantom_query {
+		commands.entity(entity).insert(Dissolving::default());
+	}
+}
+
+fn dissolve(
+	mut commands: Commands,
+	mut dissolving_query: Query<(Entity, &mut Transform, &mut Dissolving)>,
+	time: Res<Time>,
+) {
+	for (entity, mut transform, mut dissolving) in &mut dissolving_query {
+		let start_scale = *dissolving.start_scale.get_or_insert(transform.scale);
+		dissolving.age += time.delta_seconds();
+		let t = (dissolving.age / DISSOLVE_TIME).min(1.0);
+
+		// Shrinks and drifts upwards, like smoke
+		transform.scale = start_scale * (1.0 - t);
+		transform.translation.y += time.delta_seconds() * 1.5;
+		transform.rotate_y(time.delta_seconds() * 4.0);
+
+		if t >= 1.0 {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+}
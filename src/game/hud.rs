@@ -0,0 +1,263 @@
+use bevy_inspector_egui::egui::lerp;
+
+use crate::prelude::*;
+
+use super::effects::{
+	ActiveEffects, EffectEnded, EffectRefreshed, EffectRegistry, EffectStarted, EffectType,
+};
+
+pub struct HudPlugin;
+impl Plugin for HudPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_system(spawn_hud.in_schedule(OnEnter(GameState::InGame)))
+			.add_systems(
+				(
+					sync_effect_rows,
+					animate_effect_rows.after(sync_effect_rows),
+				)
+					.in_set(OnUpdate(GameState::InGame)),
+			);
+	}
+}
+
+/// Column in the top right corner, that holds a row for every active effect
+#[derive(Component)]
+pub struct EffectHud;
+
+/// One active effect in the HUD. Has no text, just a colored swatch and two bars.
+#[derive(Component)]
+pub struct EffectRow {
+	pub effect: EffectType,
+	/// Longest time left seen for the effect, the countdown bar is relative to it
+	pub full_time: f32,
+	/// Goes from 0.0 to 1.0 when added, and back to 0.0 when the effect is over
+	pub appear: f32,
+	/// Set when the effect was added again, fades out
+	pub flash: f32,
+	pub expiring: bool,
+	potency_bar: Entity,
+	countdown_bar: Entity,
+}
+
+const ROW_WIDTH: f32 = 160.0;
+const ROW_HEIGHT: f32 = 26.0;
+/// Seconds it takes for a row to slide in or out
+const APPEAR_TIME: f32 = 0.25;
+
+const ROW_COLOR: Color = Color::rgba(0.05, 0.05, 0.08, 0.6);
+const FLASH_COLOR: Color = Color::rgba(0.9, 0.9, 0.8, 0.8);
+
+fn spawn_hud(mut commands: Commands) {
+	commands.spawn((
+		Name::new("Effect HUD"),
+		EffectHud,
+		NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				position: UiRect {
+					right: Val::Px(10.0),
+					top: Val::Px(10.0),
+					..default()
+				},
+				size: Size::width(Val::Px(ROW_WIDTH)),
+				flex_direction: FlexDirection::Column,
+				overflow: Overflow::Hidden,
+				..default()
+			},
+			..default()
+		},
+	));
+}
+
+fn spawn_row(
+	commands: &mut Commands,
+	registry: &EffectRegistry,
+	effect: EffectType,
+	time_left: f32,
+) -> Entity {
+	let bar = |color: Color, height: f32| NodeBundle {
+		style: Style {
+			size: Size::new(Val::Percent(100.0), Val::Px(height)),
+			..default()
+		},
+		background_color: color.into(),
+		..default()
+	};
+	let track = |height: f32| NodeBundle {
+		style: Style {
+			size: Size::new(Val::Percent(100.0), Val::Px(height)),
+			..default()
+		},
+		background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+		..default()
+	};
+
+	let potency_bar = commands.spawn(bar(Color::rgb(0.9, 0.3, 0.9), 6.0)).id();
+	let countdown_bar = commands.spawn(bar(Color::WHITE, 4.0)).id();
+
+	let potency_track = commands.spawn(track(6.0)).add_child(potency_bar).id();
+	let countdown_track = commands.spawn(track(4.0)).add_child(countdown_bar).id();
+
+	let bars = commands
+		.spawn(NodeBundle {
+			style: Style {
+				flex_direction: FlexDirection::Column,
+				flex_grow: 1.0,
+				gap: Size::height(Val::Px(3.0)),
+				..default()
+			},
+			..default()
+		})
+		.push_children(&[potency_track, countdown_track])
+		.id();
+
+	// Quality of the effect is the frame around the swatch
+	let swatch = commands
+		.spawn(NodeBundle {
+			style: Style {
+				padding: UiRect::all(Val::Px(2.0)),
+				..default()
+			},
+			background_color: registry.quality(effect).color().into(),
+			..default()
+		})
+		.with_children(|commands| {
+			commands.spawn(NodeBundle {
+				style: Style {
+					size: Size::all(Val::Px(16.0)),
+					..default()
+				},
+				background_color: registry.color(effect).into(),
+				..default()
+			});
+		})
+		.id();
+
+	commands
+		.spawn((
+			Name::new(format!("{effect:?} Row")),
+			EffectRow {
+				effect,
+				full_time: time_left,
+				appear: 0.0,
+				flash: 0.0,
+				expiring: false,
+				potency_bar,
+				countdown_bar,
+			},
+			NodeBundle {
+				style: Style {
+					size: Size::new(Val::Px(ROW_WIDTH), Val::Px(0.0)),
+					flex_shrink: 0.0,
+					align_items: AlignItems::Center,
+					gap: Size::width(Val::Px(6.0)),
+					padding: UiRect::horizontal(Val::Px(4.0)),
+					overflow: Overflow::Hidden,
+					..default()
+				},
+				background_color: ROW_COLOR.into(),
+				..default()
+			},
+		))
+		.push_children(&[swatch, bars])
+		.id()
+}
+
+/// Adds rows for new effects, and lets the finished ones go
+fn sync_effect_rows(
+	mut commands: Commands,
+	hud_query: Query<Entity, With<EffectHud>>,
+	mut row_query: Query<&mut EffectRow>,
+	mut started_events: EventReader<EffectStarted>,
+	mut refreshed_events: EventReader<EffectRefreshed>,
+	mut ended_events: EventReader<EffectEnded>,
+	effect_registry: Res<EffectRegistry>,
+) {
+	let Ok(hud) = hud_query.get_single() else { return; };
+
+	for EffectStarted(effect) in started_events.iter() {
+		// Still fading out, bring it back
+		if let Some(mut row) = row_query.iter_mut().find(|r| r.effect == effect.effect) {
+			row.full_time = effect.time_left;
+			row.flash = 1.0;
+			row.expiring = false;
+			continue;
+		}
+
+		let row = spawn_row(
+			&mut commands,
+			&effect_registry,
+			effect.effect,
+			effect.time_left,
+		);
+		commands.entity(hud).add_child(row);
+	}
+
+	for EffectRefreshed(effect) in refreshed_events.iter() {
+		let Some(mut row) = row_query.iter_mut().find(|r| r.effect == effect.effect) else { continue; };
+
+		// Countdown starts over, if it got longer
+		row.full_time = row.full_time.max(effect.time_left);
+		row.flash = 1.0;
+	}
+
+	for EffectEnded(effect) in ended_events.iter() {
+		for mut row in row_query.iter_mut().filter(|r| r.effect == *effect) {
+			row.expiring = true;
+		}
+	}
+}
+
+fn animate_effect_rows(
+	mut commands: Commands,
+	mut row_query: Query<(Entity, &mut EffectRow, &mut Style, &mut BackgroundColor)>,
+	mut bar_query: Query<&mut Style, Without<EffectRow>>,
+	active_effects: Res<ActiveEffects>,
+	time: Res<Time>,
+) {
+	for (entity, mut row, mut style, mut background) in &mut row_query {
+		let step = time.delta_seconds() / APPEAR_TIME;
+		row.appear = if row.expiring {
+			row.appear - step
+		} else {
+			row.appear + step
+		}
+		.clamp(0.0, 1.0);
+		row.flash = (row.flash - time.delta_seconds() * 2.0).max(0.0);
+
+		if row.expiring && row.appear <= 0.0 {
+			commands.entity(entity).despawn_recursive();
+			continue;
+		}
+
+		// Grows in height and slides in from the right
+		let t = row.appear * row.appear * (3.0 - 2.0 * row.appear);
+		style.size.height = Val::Px(ROW_HEIGHT * t);
+		style.margin.top = Val::Px(4.0 * t);
+		style.position.left = Val::Px(ROW_WIDTH * (1.0 - t));
+
+		let [r, g, b, a] = ROW_COLOR.as_rgba_f32();
+		let [fr, fg, fb, fa] = FLASH_COLOR.as_rgba_f32();
+		background.0 = Color::rgba(
+			lerp(r..=fr, row.flash),
+			lerp(g..=fg, row.flash),
+			lerp(b..=fb, row.flash),
+			lerp(a..=fa, row.flash),
+		);
+
+		let (potency, countdown) = match active_effects.has_effect(row.effect) {
+			Some(effect) => (
+				effect.potency,
+				(effect.time_left / row.full_time.max(0.001)).clamp(0.0, 1.0),
+			),
+			None => (0.0, 0.0),
+		};
+
+		if let Ok(mut bar) = bar_query.get_mut(row.potency_bar) {
+			bar.size.width = Val::Percent(potency.clamp(0.0, 1.0) * 100.0);
+		}
+		if let Ok(mut bar) = bar_query.get_mut(row.countdown_bar) {
+			bar.size.width = Val::Percent(countdown * 100.0);
+		}
+	}
+}
@@ -1,37 +1,128 @@
-use crate::prelude::*;
+use std::ops::Range;
 
-use super::effects::EffectType;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Component, Reflect, FromReflect, PartialEq)]
+use crate::{
+	assets::{Spawnable, SpawnableArchetype},
+	prelude::*,
+};
+
+use super::effects::{EffectQuality, EffectRegistry, EffectType};
+
+#[derive(Debug, Clone, Copy, Reflect, FromReflect, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "SavedGrind")]
 pub enum Grind {
-	Grinding(f32),
+	#[default]
+	Whole,
+	Coarse,
+	Fine,
+	/// Ground for too long. Still brews, but badly
+	Paste,
+}
+
+/// Grind as it was saved, including levels from older journals
+#[derive(Deserialize)]
+enum SavedGrind {
+	Whole,
+	Coarse,
+	Fine,
+	Paste,
+	/// Old, still being ground
+	Grinding(#[allow(dead_code)] f32),
+	/// Old, crushed all the way
 	Grinded,
 }
 
-impl Default for Grind {
-	fn default() -> Self {
-		Self::Grinding(0.0)
+impl From<SavedGrind> for Grind {
+	fn from(saved: SavedGrind) -> Self {
+		match saved {
+			SavedGrind::Whole | SavedGrind::Grinding(_) => Grind::Whole,
+			SavedGrind::Coarse | SavedGrind::Grinded => Grind::Coarse,
+			SavedGrind::Fine => Grind::Fine,
+			SavedGrind::Paste => Grind::Paste,
+		}
+	}
+}
+
+impl Grind {
+	/// Level, that grinding for long enough leads to, and how many seconds it takes
+	pub fn next(self) -> Option<(Grind, f32)> {
+		match self {
+			Grind::Whole => Some((Grind::Coarse, 0.35)),
+			Grind::Coarse => Some((Grind::Fine, 0.35)),
+			// Give some time to stop before it's ruined
+			Grind::Fine => Some((Grind::Paste, 0.6)),
+			Grind::Paste => None,
+		}
+	}
+
+	pub fn quality_bonus(self) -> f32 {
+		match self {
+			Grind::Whole => 0.0,
+			Grind::Coarse => 0.05,
+			Grind::Fine => 0.10,
+			Grind::Paste => -0.05,
+		}
+	}
+
+	pub fn potency_mult(self) -> f32 {
+		match self {
+			Grind::Whole => 1.0,
+			Grind::Coarse => 1.1,
+			Grind::Fine => 1.2,
+			Grind::Paste => 0.8,
+		}
+	}
+
+	/// How many of the ingredient's effects make it into the brew
+	pub fn extracted_effects(self) -> usize {
+		match self {
+			Grind::Whole => 1,
+			Grind::Coarse => 2,
+			Grind::Fine | Grind::Paste => usize::MAX,
+		}
+	}
+
+	/// Paste lets the effects out too quickly, so they don't last
+	pub fn duration_mult(self) -> f32 {
+		match self {
+			Grind::Paste => 0.5,
+			_ => 1.0,
+		}
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Grind::Whole => "whole",
+			Grind::Coarse => "coarse",
+			Grind::Fine => "fine",
+			Grind::Paste => "paste",
+		}
 	}
 }
 
 #[derive(Debug, Clone, Component, Default, Reflect, FromReflect)]
 pub struct Ingredient {
 	pub ingredient_type: IngredientType,
+	/// Index of the species in `SpeciesRegistry`
+	pub species: usize,
 	pub name: String,
 	pub is_rare: bool,
 	pub color: Color,
 	pub grind: Grind,
+	/// Seconds spent grinding towards the next `Grind` level
+	pub grind_progress: f32,
 	pub size: f32,
 	pub effects: SmallVec<[IngredientEffect; 4]>,
 }
 
-#[derive(Clone, Copy, Debug, Reflect, FromReflect)]
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
 pub struct IngredientEffect {
 	pub effect_type: EffectType,
 	pub duration: f32,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Reflect, FromReflect)]
 // TODO_OLEG: Add more ingredient types
 pub enum IngredientType {
@@ -42,20 +133,143 @@ pub enum IngredientType {
 	Root,
 }
 
-impl Ingredient {
-	#[allow(dead_code)]
-	// TODO_OLEG: Generate random ingredients
-	// Unused :(
-	pub fn generate_random_ingredient(
+/// Kind of ingredient, generated once per world.
+/// Every ingredient of the same species has the same name, looks and effects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngredientSpecies {
+	pub ingredient_type: IngredientType,
+	pub name: String,
+	/// Spawnable (archetype and id) this species grows on
+	pub spawnable: (SpawnableArchetype, usize),
+	/// Hue range of the species main color
+	pub hue: Range<f32>,
+	pub base_size: f32,
+	pub effects: SmallVec<[IngredientEffect; 4]>,
+}
+
+/// All ingredient species of the current world
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SpeciesRegistry(pub Vec<IngredientSpecies>);
+
+impl SpeciesRegistry {
+	/// Species for every spawnable with an ingredient. Spawnables have to always come in the same order.
+	pub fn generate(
+		spawnables: &[&Spawnable],
+		effect_registry: &EffectRegistry,
+		rng: &mut StdRng,
+	) -> Self {
+		const SPECIES_PER_SPAWNABLE: usize = 3;
+
+		let mut species_registry = SpeciesRegistry::default();
+
+		for spawnable in spawnables {
+			if spawnable.ingredient.is_none() {
+				continue;
+			}
+
+			let (ingredient_type, hue) = match spawnable.archetype {
+				SpawnableArchetype::Bush => (IngredientType::Berry, 190.0..360.0),
+				SpawnableArchetype::Mushroom => (IngredientType::Mushroom, 0.0..360.0),
+				SpawnableArchetype::Root => (IngredientType::Root, 10.0..60.0),
+				SpawnableArchetype::Plant => (IngredientType::Plant, 0.0..360.0),
+				SpawnableArchetype::Tree => continue,
+			};
+
+			// Species of the same spawnable are told apart by their colors
+			let hue_step = (hue.end - hue.start) / SPECIES_PER_SPAWNABLE as f32;
+
+			for i in 0..SPECIES_PER_SPAWNABLE {
+				let hue_start = hue.start + hue_step * i as f32;
+
+				species_registry.add_species(
+					rng,
+					ingredient_type,
+					(spawnable.archetype, spawnable.id),
+					hue_start..hue_start + hue_step,
+					effect_registry,
+				);
+			}
+		}
+
+		species_registry
+	}
+
+	/// Adds new species, that will grow on the spawnable
+	pub fn add_species(
+		&mut self,
 		rng: &mut impl Rng,
 		ingredient_type: IngredientType,
+		spawnable: (SpawnableArchetype, usize),
+		hue: Range<f32>,
+		effect_registry: &EffectRegistry,
+	) {
+		// Try not to give two species the same name
+		let mut name = IngredientSpecies::generate_name(rng, ingredient_type);
+		for _ in 0..10 {
+			if self.0.iter().all(|s| s.name != name) {
+				break;
+			}
+			name = IngredientSpecies::generate_name(rng, ingredient_type);
+		}
+
+		self.0.push(IngredientSpecies {
+			ingredient_type,
+			name,
+			spawnable,
+			hue,
+			base_size: rng.gen_range(0.85..1.15),
+			effects: IngredientSpecies::generate_effects(rng, effect_registry),
+		});
+	}
+
+	pub fn get(&self, id: usize) -> Option<&IngredientSpecies> {
+		self.0.get(id)
+	}
+
+	/// Randomly chooses species, that grows on the spawnable
+	pub fn random_for_spawnable(
+		&self,
+		spawnable: (SpawnableArchetype, usize),
+		rng: &mut impl Rng,
+	) -> Option<(usize, &IngredientSpecies)> {
+		self.0
+			.iter()
+			.enumerate()
+			.filter(|(_, s)| s.spawnable == spawnable)
+			.choose(rng)
+	}
+}
+
+impl Ingredient {
+	/// Hurts when eaten raw
+	pub fn is_poisonous(&self) -> bool {
+		self.effects
+			.iter()
+			.any(|e| e.effect_type == EffectType::POISON)
+	}
+
+	pub fn from_species(
+		species_id: usize,
+		species: &IngredientSpecies,
 		is_rare: bool,
 		color: Color,
 		size: f32,
 	) -> Self {
-		let name;
-		let effects;
+		Ingredient {
+			ingredient_type: species.ingredient_type,
+			species: species_id,
+			name: species.name.clone(),
+			effects: species.effects.clone(),
+			color,
+			is_rare,
+			size,
+			..default()
+		}
+	}
+}
 
+impl IngredientSpecies {
+	pub fn generate_name(rng: &mut impl Rng, ingredient_type: IngredientType) -> String {
 		match ingredient_type {
 			IngredientType::Mushroom => {
 				const NAME_1: &[&str] = &[
@@ -69,12 +283,11 @@ impl Ingredient {
 				];
 				const NAME_2: &[&str] = &["Toe", "Falloides", "Uscaria", "Ubensis", "Azuresense"];
 
-				name = format!(
+				format!(
 					"{} {}",
 					NAME_1[rng.gen_range(0..NAME_1.len())],
 					NAME_2[rng.gen_range(0..NAME_2.len())]
-				);
-				effects = SmallVec::new();
+				)
 			}
 			// More types here...
 			IngredientType::Berry => {
@@ -95,12 +308,11 @@ impl Ingredient {
 				];
 				const NAME_2: &[&str] = &["berry"];
 
-				name = format!(
+				format!(
 					"{}{}",
 					NAME_1[rng.gen_range(0..NAME_1.len())],
 					NAME_2[rng.gen_range(0..NAME_2.len())]
-				);
-				effects = SmallVec::new();
+				)
 			}
 			IngredientType::Plant => {
 				const NAME_1: &[&str] = &[
@@ -111,12 +323,11 @@ impl Ingredient {
 					"fang", "horn",
 				];
 
-				name = format!(
+				format!(
 					"{} {}",
 					NAME_1[rng.gen_range(0..NAME_1.len())],
 					NAME_2[rng.gen_range(0..NAME_2.len())]
-				);
-				effects = SmallVec::new();
+				)
 			}
 			IngredientType::Root => {
 				const NAME_1: &[&str] = &[
@@ -135,23 +346,55 @@ impl Ingredient {
 				];
 				const NAME_2: &[&str] = &["root"];
 
-				name = format!(
+				format!(
 					"{}{}",
 					NAME_1[rng.gen_range(0..NAME_1.len())],
 					NAME_2[rng.gen_range(0..NAME_2.len())]
-				);
-				effects = SmallVec::new();
+				)
 			}
 		}
+	}
 
-		Ingredient {
-			name,
-			effects,
-			color,
-			is_rare,
-			ingredient_type,
-			size,
-			..default()
+	fn generate_effects(
+		rng: &mut impl Rng,
+		effect_registry: &EffectRegistry,
+	) -> SmallVec<[IngredientEffect; 4]> {
+		(0..rng.gen_range(1..=2))
+			.filter_map(|_| {
+				let roll = rng.gen::<f32>();
+				let effect = effect_registry.generate(EffectQuality::from_roll(roll), rng)?;
+
+				Some(IngredientEffect {
+					effect_type: effect.effect,
+					duration: rng.gen_range(effect.duration.clone()),
+				})
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn grind_reads_current_levels() {
+		for grind in [Grind::Whole, Grind::Coarse, Grind::Fine, Grind::Paste] {
+			let saved = ron::to_string(&grind).unwrap();
+			assert_eq!(ron::from_str::<Grind>(&saved).unwrap(), grind);
 		}
 	}
+
+	#[test]
+	fn grind_reads_old_journals() {
+		assert_eq!(
+			ron::from_str::<Grind>("Grinding(0.0)").unwrap(),
+			Grind::Whole
+		);
+		assert_eq!(
+			ron::from_str::<Grind>("Grinding(0.7)").unwrap(),
+			Grind::Whole
+		);
+		assert_eq!(ron::from_str::<Grind>("Grinded").unwrap(), Grind::Coarse);
+	}
 }
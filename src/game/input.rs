@@ -16,8 +16,11 @@ pub fn default_inputs() -> InputManagerBundle<Action> {
 			.insert(DualAxis::left_stick(), Action::Move)
 			.insert(VirtualDPad::wasd(), Action::Move)
 			.insert(QwertyScanCode::E, Action::Use)
+			.insert(QwertyScanCode::Q, Action::Dig)
 			.insert(QwertyScanCode::Space, Action::OpenCrafring)
+			.insert(QwertyScanCode::J, Action::OpenJournal)
 			.insert(GamepadButtonType::RightTrigger2, Action::Use)
+			.insert(GamepadButtonType::West, Action::Dig)
 			.insert(DualAxis::right_stick(), Action::Look)
 			.insert(DualAxis::mouse_motion(), Action::Look)
 			.insert(MouseButton::Right, Action::ActivateLook)
@@ -31,10 +34,12 @@ pub enum Action {
 	Click,
 	Move,
 	Use,
+	Dig,
 	Look,
 	ActivateLook,
 	Zoom,
 	OpenCrafring,
+	OpenJournal,
 }
 
 pub fn cursor_grab_system(
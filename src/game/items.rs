@@ -8,9 +8,16 @@ use crate::{
 
 use super::{
 	backpack::InventoryCamera,
-	effects::{ActiveEffects, EffectType},
-	ingredient::Ingredient,
+	effects::{
+		generate_effects_from_ingredients, generate_qp_from_ingredients, ActiveEffects, Effect,
+		EffectAppExt, EffectDef, EffectQuality, EffectRegistry, EffectType, StackRule,
+	},
+	hallucinations::{Dissolving, Phantom},
+	ingredient::{Ingredient, SpeciesRegistry},
 	player::Player,
+	regrowth::HarvestEvent,
+	splash::SplashPotion,
+	thief::Stolen,
 	world::SpawnableInstance,
 };
 
@@ -25,6 +32,8 @@ pub struct ItemSize {
 	pub shrinking: bool,
 	pub size_mult: f32,
 	pub mouth_mult: f32,
+	/// Set by potion effects
+	pub effect_mult: f32,
 	current_size: f32,
 }
 
@@ -40,6 +49,7 @@ impl ItemSize {
 			size_mult: size_multiplier,
 			shrinking,
 			mouth_mult: 1.0,
+			effect_mult: 1.0,
 			current_size: if shrinking {
 				1.0 * size_multiplier
 			} else {
@@ -50,7 +60,7 @@ impl ItemSize {
 
 	pub fn reset(&mut self) {
 		self.current_size = if self.shrinking {
-			1.0 * self.size_mult * self.mouth_mult
+			1.0 * self.size_mult * self.mouth_mult * self.effect_mult
 		} else {
 			0.0
 		}
@@ -64,7 +74,7 @@ impl ItemSize {
 		if self.shrinking {
 			0.01
 		} else {
-			1.0 * self.size_mult * self.mouth_mult
+			1.0 * self.size_mult * self.mouth_mult * self.effect_mult
 		}
 	}
 }
@@ -83,9 +93,32 @@ pub enum Potion {
 	Filled {
 		ingridients: Vec<Ingredient>,
 		color: Color,
+		/// Seconds the brew was simmering before it was bottled
+		simmer_time: f32,
+		/// How full the bottle is, from 0.0 to 1.0
+		dose: f32,
 	},
 }
 
+impl Potion {
+	/// Quality, potency and effects of the potion. `None` for an empty bottle.
+	pub fn brew(&self, registry: &EffectRegistry) -> Option<(f32, f32, Vec<Effect>)> {
+		let Potion::Filled { ingridients, simmer_time, dose, .. } = self else {
+			return None;
+		};
+
+		let (quality, potency) = generate_qp_from_ingredients(ingridients, *simmer_time);
+		// Half empty bottle is only half as strong
+		let potency = potency * dose;
+
+		Some((
+			quality,
+			potency,
+			generate_effects_from_ingredients(ingridients, quality, potency, registry),
+		))
+	}
+}
+
 // Thats a chunky boy :)
 #[derive(Bundle, Clone)]
 pub struct InventoryItemBundle {
@@ -161,11 +194,21 @@ impl Plugin for ItemsPlugin {
 				.in_set(OnUpdate(GameState::InGame)),
 		)
 		.add_system(move_grabber.in_base_set(CoreSet::FixedUpdate))
+		.add_effect(EffectDef {
+			stack_rule: StackRule::CancelOpposite,
+			opposite: Some(EffectType::THIEF),
+			color: Color::rgb(0.3, 0.9, 0.3),
+			..EffectDef::new(EffectType::LUCKY_HANDS, EffectQuality::Positive)
+		})
 		.register_type::<Item>()
 		.register_type::<ItemSize>();
 	}
 }
 
+impl EffectType {
+	pub const LUCKY_HANDS: EffectType = EffectType("LuckyHands");
+}
+
 fn init(mut commands: Commands) {
 	commands.spawn((
 		Grabber::default(),
@@ -183,7 +226,10 @@ fn init(mut commands: Commands) {
 	));
 }
 
-fn animate_size(mut inventory_item_query: Query<(&mut Transform, &mut ItemSize)>, time: Res<Time>) {
+fn animate_size(
+	mut inventory_item_query: Query<(&mut Transform, &mut ItemSize), Without<Dissolving>>,
+	time: Res<Time>,
+) {
 	for (mut transform, mut item_size) in inventory_item_query.iter_mut() {
 		item_size.current_size = lerp(
 			item_size.current_size..=item_size.desired_size(),
@@ -230,31 +276,40 @@ fn drop_items(
 
 				//transform.scale = Vec3::splat(0.01);
 			}
-			Item::Ingredient | Item::Potion(Potion::Filled { .. }) => {
-				let player_translation = player_query.single().translation;
+			// Filled potions thrown over the top shatter on impact
+			Item::Potion(Potion::Filled { .. }) if transform.translation.y > 8.5 => {
+				let player_transform = player_query.single();
 
 				*velocity = Velocity {
-					angvel: Vec3 {
-						x: rng.gen_range(-8.0..8.0),
-						y: rng.gen_range(-8.0..8.0),
-						z: rng.gen_range(-8.0..8.0),
-					},
-					linvel: Vec3 {
-						x: rng.gen_range(-6.0..6.0),
-						y: 10.0,
-						z: rng.gen_range(-6.0..6.0),
-					},
+					angvel: Vec3::new(rng.gen_range(-8.0..8.0), 0.0, rng.gen_range(-8.0..8.0)),
+					linvel: player_transform.rotation * Vec3::Z * 10.0 + Vec3::Y * 6.0,
 				};
 
 				item_size.reset();
 
 				commands
 					.entity(dropped_item)
-					.insert(DroppedItem::default())
+					.insert(DroppedItem)
+					.insert(SplashPotion)
+					.insert(ActiveEvents::COLLISION_EVENTS)
 					.insert(RenderLayers::layer(0))
 					.insert(CollisionGroups::new(Group::GROUP_3, Group::GROUP_1));
 
-				transform.translation = player_translation + Vec3::Y;
+				transform.translation = player_transform.translation + Vec3::Y * 1.5;
+
+				sound.play(game_assets.drop_item_sound.clone());
+			}
+			Item::Ingredient | Item::Potion(Potion::Filled { .. }) => {
+				let player_translation = player_query.single().translation;
+
+				drop_item(
+					&mut commands,
+					dropped_item,
+					&mut transform,
+					&mut item_size,
+					&mut velocity,
+					player_translation + Vec3::Y,
+				);
 
 				sound.play(game_assets.drop_item_sound.clone());
 				//transform.scale = Vec3::splat(0.01);
@@ -263,6 +318,41 @@ fn drop_items(
 	}
 }
 
+/// Moves an item out of the backpack into the world, tossing it up from `translation`
+pub fn drop_item(
+	commands: &mut Commands,
+	entity: Entity,
+	transform: &mut Transform,
+	item_size: &mut ItemSize,
+	velocity: &mut Velocity,
+	translation: Vec3,
+) {
+	let mut rng = thread_rng();
+
+	*velocity = Velocity {
+		angvel: Vec3 {
+			x: rng.gen_range(-8.0..8.0),
+			y: rng.gen_range(-8.0..8.0),
+			z: rng.gen_range(-8.0..8.0),
+		},
+		linvel: Vec3 {
+			x: rng.gen_range(-6.0..6.0),
+			y: 10.0,
+			z: rng.gen_range(-6.0..6.0),
+		},
+	};
+
+	item_size.reset();
+
+	commands
+		.entity(entity)
+		.insert(DroppedItem)
+		.insert(RenderLayers::layer(0))
+		.insert(CollisionGroups::new(Group::GROUP_3, Group::GROUP_1));
+
+	transform.translation = translation;
+}
+
 pub fn pickup_entity(
 	mut commands: Commands,
 	player_query: Query<(&Transform, &ActionState<Action>), (With<Player>, Without<DroppedItem>)>,
@@ -274,17 +364,25 @@ pub fn pickup_entity(
 			&SpawnableInstance,
 			&NamedMaterials,
 		),
-		Without<DroppedItem>,
+		(Without<DroppedItem>, Without<Dissolving>),
 	>,
 	mut dropped_item_query: Query<
 		(Entity, &mut Transform, &mut ItemSize, &mut Velocity),
-		With<DroppedItem>,
+		(
+			With<DroppedItem>,
+			Without<SplashPotion>,
+			Without<Stolen>,
+			Without<Dissolving>,
+		),
 	>,
+	phantom_query: Query<(), With<Phantom>>,
 	finder_query: Query<(Entity, &Name)>,
 	child_query: Query<&Children>,
 	spawnables: Res<Assets<Spawnable>>,
+	species_registry: Res<SpeciesRegistry>,
 	game_assets: Res<GameAssets>,
 	active_effects: Res<ActiveEffects>,
+	mut harvest_events: EventWriter<HarvestEvent>,
 	mut hallucination_message: Local<bool>,
 	sound: Res<Audio>,
 ) {
@@ -292,10 +390,19 @@ pub fn pickup_entity(
 		return;
 	};
 
-	if !input.just_pressed(Action::Use) {
+	let digging = input.just_pressed(Action::Dig);
+	if !input.just_pressed(Action::Use) && !digging {
 		return;
 	}
 
+	// Roots have to be dug out, everything else is just picked up
+	let needs_digging = |instance: &SpawnableInstance| {
+		spawnables
+			.get(&instance.handle)
+			.and_then(|s| s.ingredient.as_ref())
+			.is_some_and(|i| i.dig)
+	};
+
 	#[derive(Debug)]
 	enum Interactable {
 		Pickupable,
@@ -304,10 +411,12 @@ pub fn pickup_entity(
 
 	let closest_interactable = ingredient_query
 		.iter()
+		.filter(|q| needs_digging(q.3) == digging)
 		.map(|q| (q.0, q.1, Interactable::Pickupable))
 		.chain(
 			dropped_item_query
 				.iter()
+				.filter(|_| !digging)
 				.map(|q| (q.0, q.1, Interactable::DroppedItem)),
 		)
 		.filter_map(|c| {
@@ -327,10 +436,17 @@ pub fn pickup_entity(
 	match closest_interactable {
 		None => (),
 		Some((entity, _, Interactable::Pickupable)) => {
-			let Ok((entity,_ , ingredient, spawnable_instance, named_materials)) = ingredient_query.get(entity) else {
+			let Ok((entity, transform, ingredient, spawnable_instance, named_materials)) = ingredient_query.get(entity) else {
 				return;
 			};
 
+			// Never was there
+			if phantom_query.contains(entity) {
+				commands.entity(entity).insert(Dissolving::default());
+				sound.play(game_assets.wha_sound.clone());
+				return;
+			}
+
 			let Some(spawnable) = spawnables.get(&spawnable_instance.handle) else {
 				warn!("Did not find spawnable in assets!");
 				return;
@@ -339,10 +455,21 @@ pub fn pickup_entity(
 			let ingredient_info = spawnable.ingredient.as_ref().unwrap();
 
 			// Hallucinations
-			if let Some(hallucination) = active_effects.has_effect(EffectType::Hallucinations) {
+			if let Some(hallucination) = active_effects.has_effect(EffectType::HALLUCINATIONS) {
 				let mut rng = thread_rng();
-				if rng.gen_bool(lerp(0.2..=0.5, hallucination.potency as f64)) {
-					commands.entity(entity).despawn_recursive();
+				if rng.gen_bool(lerp(0.1..=0.25, hallucination.potency as f64)) {
+					// Turns out it was never real either
+					commands
+						.entity(entity)
+						.remove::<Ingredient>()
+						.insert(Dissolving::default());
+					harvest_events.send(HarvestEvent {
+						entity,
+						spawnable: spawnable_instance.handle.clone(),
+						position: transform.translation,
+						species: ingredient.species,
+						vanished: true,
+					});
 					if !*hallucination_message {
 						sound.play(game_assets.insanity_sound.clone());
 						*hallucination_message = true;
@@ -367,51 +494,89 @@ pub fn pickup_entity(
 				PickUpEvent::RemoveNamedChild(name) => {
 					commands.entity(entity).remove::<Ingredient>();
 
+					// Only hidden, so it can grow back
 					for (child, child_name) in child_query
 						.iter_descendants(entity)
 						.filter_map(|child| finder_query.get(child).ok())
 					{
 						if child_name.contains(name) {
-							commands.entity(child).despawn();
+							commands.entity(child).insert(Visibility::Hidden);
 						}
 					}
 				}
 			}
 
+			harvest_events.send(HarvestEvent {
+				entity,
+				spawnable: spawnable_instance.handle.clone(),
+				position: transform.translation,
+				species: ingredient.species,
+				vanished: false,
+			});
+
 			let mut rng = thread_rng();
 
-			let size = spawnable_instance.size;
-
-			commands.spawn((
-				Name::new(ingredient.name.clone()),
-				InventoryItemBundle {
-					scene: ingredient_info.inventory_scene.clone(),
-					transform: Transform::from_xyz(
-						rng.gen_range(-0.5..0.5),
-						2.0 + rng.gen_range(-0.5..0.5),
-						0.0,
-					)
-					.with_scale(Vec3::splat(0.01)),
-					velocity: Velocity {
-						angvel: Vec3::new(
-							rng.gen_range(-5.0..5.0),
-							rng.gen_range(-5.0..5.0),
-							rng.gen_range(-5.0..5.0),
-						),
+			let mut ingredient = ingredient.clone();
+			let mut named_materials = named_materials.clone();
+			let mut size = spawnable_instance.size;
+			let mut count = 1;
+
+			// Lucky hands find rare ones, and sometimes grab two at once
+			if let Some(lucky) = active_effects.has_effect(EffectType::LUCKY_HANDS) {
+				if !ingredient.is_rare && rng.gen_bool(lerp(0.05..=0.3, lucky.potency as f64)) {
+					let (materials, color) = NamedMaterials::generate_materials(
+						spawnable.archetype,
+						true,
+						species_registry
+							.get(ingredient.species)
+							.map(|s| s.hue.clone()),
+						&mut rng,
+					);
+
+					named_materials = materials;
+					ingredient.color = color;
+					ingredient.is_rare = true;
+					size *= 1.3;
+					ingredient.size = size;
+				}
+
+				if rng.gen_bool(lerp(0.1..=0.5, lucky.potency as f64)) {
+					count = 2;
+				}
+			}
+
+			for _ in 0..count {
+				commands.spawn((
+					Name::new(ingredient.name.clone()),
+					InventoryItemBundle {
+						scene: ingredient_info.inventory_scene.clone(),
+						transform: Transform::from_xyz(
+							rng.gen_range(-0.5..0.5),
+							2.0 + rng.gen_range(-0.5..0.5),
+							0.0,
+						)
+						.with_scale(Vec3::splat(0.01)),
+						velocity: Velocity {
+							angvel: Vec3::new(
+								rng.gen_range(-5.0..5.0),
+								rng.gen_range(-5.0..5.0),
+								rng.gen_range(-5.0..5.0),
+							),
+							..default()
+						},
+						inventory_item: Item::Ingredient,
+						item_size: ItemSize::new(size, false),
+						collider: ingredient_info.collider.clone(),
 						..default()
 					},
-					inventory_item: Item::Ingredient,
-					item_size: ItemSize::new(size, false),
-					collider: ingredient_info.collider.clone(),
-					..default()
-				},
-				ingredient.clone(),
-				named_materials.clone(),
-				Damping {
-					linear_damping: 0.5,
-					angular_damping: 0.7,
-				},
-			));
+					ingredient.clone(),
+					named_materials.clone(),
+					Damping {
+						linear_damping: 0.5,
+						angular_damping: 0.7,
+					},
+				));
+			}
 
 			sound.play(game_assets.pickup_sound.choose(&mut rng).unwrap().clone());
 
@@ -427,9 +592,9 @@ pub fn pickup_entity(
 			let mut rng = thread_rng();
 
 			// Hallucinations
-			if let Some(hallucination) = active_effects.has_effect(EffectType::Hallucinations) {
-				if rng.gen_bool(lerp(0.2..=0.5, hallucination.potency as f64)) {
-					commands.entity(entity).despawn_recursive();
+			if let Some(hallucination) = active_effects.has_effect(EffectType::HALLUCINATIONS) {
+				if rng.gen_bool(lerp(0.1..=0.25, hallucination.potency as f64)) {
+					commands.entity(entity).insert(Dissolving::default());
 					if !*hallucination_message {
 						sound.play(game_assets.insanity_sound.clone());
 						*hallucination_message = true;
@@ -0,0 +1,405 @@
+use bevy_inspector_egui::bevy_egui::{egui, EguiContexts, EguiPlugin};
+use serde::{
+	de::{DeserializeSeed, EnumAccess, VariantAccess, Visitor},
+	Deserialize, Deserializer, Serialize,
+};
+use std::fmt;
+
+use crate::prelude::*;
+
+use super::{
+	effects::{Effect, EffectRegistry, EffectType},
+	ingredient::{Grind, Ingredient},
+	world::WorldSeed,
+};
+
+pub struct JournalPlugin;
+impl Plugin for JournalPlugin {
+	fn build(&self, app: &mut App) {
+		// Always added here, the inspector in debug builds comes later and reuses it
+		if !app.is_plugin_added::<EguiPlugin>() {
+			app.add_plugin(EguiPlugin);
+		}
+
+		// Effects are looked up by name, so every plugin has to register its effects first
+		app.add_startup_system(load_journal)
+			.init_resource::<JournalView>()
+			.add_systems(
+				(
+					toggle_journal,
+					show_journal.after(toggle_journal),
+					save_journal,
+				)
+					.in_set(OnUpdate(GameState::InGame)),
+			);
+	}
+}
+
+/// Journal is saved next to the game, so it survives between sessions
+#[cfg(not(target_arch = "wasm32"))]
+const JOURNAL_PATH: &str = "journal.ron";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalIngredient {
+	pub name: String,
+	pub is_rare: bool,
+	pub grind: Grind,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct JournalEffect<E = EffectType> {
+	pub effect: E,
+	pub potency: f32,
+	pub duration: f32,
+}
+
+/// Single brewed and drunk potion
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalEntry<E = EffectType> {
+	pub ingredients: Vec<JournalIngredient>,
+	pub quality: f32,
+	pub potency: f32,
+	pub effects: Vec<JournalEffect<E>>,
+	/// Ingredient names are rolled per world, so they only make sense with the world they came from
+	pub world_seed: u64,
+}
+
+impl JournalEntry {
+	pub fn new(
+		ingredients: &[Ingredient],
+		quality: f32,
+		potency: f32,
+		effects: &[Effect],
+		world_seed: WorldSeed,
+	) -> Self {
+		JournalEntry {
+			ingredients: ingredients
+				.iter()
+				.map(|i| JournalIngredient {
+					name: i.name.clone(),
+					is_rare: i.is_rare,
+					grind: i.grind,
+				})
+				.collect(),
+			quality,
+			potency,
+			effects: effects
+				.iter()
+				.map(|e| JournalEffect {
+					effect: e.effect,
+					potency: e.potency,
+					duration: e.time_left,
+				})
+				.collect(),
+			world_seed: world_seed.0,
+		}
+	}
+
+	/// Short instruction on how to brew the same potion again
+	pub fn rebrew_hint(&self) -> String {
+		let steps = self
+			.ingredients
+			.iter()
+			.map(|i| {
+				format!(
+					"{} {}{}",
+					i.grind.name(),
+					if i.is_rare { "rare " } else { "" },
+					i.name
+				)
+			})
+			.collect::<Vec<_>>();
+
+		format!(
+			"Add {} (world seed {})",
+			steps.join(", then "),
+			self.world_seed
+		)
+	}
+}
+
+/// All potions player has ever drunk
+#[derive(Resource, Clone, Debug, Default, Serialize)]
+pub struct RecipeJournal(pub Vec<JournalEntry>);
+
+/// Effect as it was saved, written like the variant of the old `EffectType` enum
+#[derive(Clone, Debug)]
+struct EffectName(String);
+
+impl<'de> Deserialize<'de> for EffectName {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct NameVisitor;
+
+		impl<'de> Visitor<'de> for NameVisitor {
+			type Value = EffectName;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("name of an effect")
+			}
+
+			fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<EffectName, E> {
+				Ok(EffectName(name.to_string()))
+			}
+
+			fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<EffectName, A::Error> {
+				let (name, variant) = data.variant_seed(NameVisitor)?;
+				variant.unit_variant()?;
+				Ok(name)
+			}
+		}
+
+		// The variant itself is only an identifier
+		impl<'de> DeserializeSeed<'de> for NameVisitor {
+			type Value = EffectName;
+
+			fn deserialize<D: Deserializer<'de>>(
+				self,
+				deserializer: D,
+			) -> Result<EffectName, D::Error> {
+				deserializer.deserialize_identifier(self)
+			}
+		}
+
+		// Any name is fine, unknown ones are left out once the journal is loaded
+		deserializer.deserialize_enum("EffectType", &[], NameVisitor)
+	}
+}
+
+/// Journal as it was saved, before its effects are looked up in the `EffectRegistry`
+#[derive(Deserialize)]
+struct SavedJournal(Vec<JournalEntry<EffectName>>);
+
+impl SavedJournal {
+	/// Effects that are not in the game anymore are left out
+	fn resolve(self, registry: &EffectRegistry) -> RecipeJournal {
+		let entries = self.0.into_iter().map(|entry| JournalEntry {
+			ingredients: entry.ingredients,
+			quality: entry.quality,
+			potency: entry.potency,
+			effects: entry
+				.effects
+				.into_iter()
+				.filter_map(|e| {
+					let Some(effect) = registry.find(&e.effect.0) else {
+						warn!(
+							"Unknown effect {} in recipe journal, leaving it out",
+							e.effect.0
+						);
+						return None;
+					};
+
+					Some(JournalEffect {
+						effect,
+						potency: e.potency,
+						duration: e.duration,
+					})
+				})
+				.collect(),
+			world_seed: entry.world_seed,
+		});
+
+		RecipeJournal(entries.collect())
+	}
+}
+
+impl RecipeJournal {
+	#[cfg(not(target_arch = "wasm32"))]
+	fn load(registry: &EffectRegistry) -> Self {
+		let Ok(journal) = std::fs::read_to_string(JOURNAL_PATH) else {
+			return default();
+		};
+
+		let saved = ron::from_str::<SavedJournal>(&journal).map(|saved| saved.resolve(registry));
+		saved.unwrap_or_else(|err| {
+			// Keep it around, the next save would overwrite it
+			let backup = format!("{JOURNAL_PATH}.bak");
+			match std::fs::copy(JOURNAL_PATH, &backup) {
+				Ok(_) => warn!("Couldn't read recipe journal, starting a new one: {err}. Old one was copied to {backup}"),
+				Err(copy_err) => warn!("Couldn't read recipe journal: {err}. Couldn't back it up either: {copy_err}"),
+			}
+			default()
+		})
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	fn save(&self) {
+		let journal = match ron::ser::to_string_pretty(self, default()) {
+			Ok(journal) => journal,
+			Err(err) => {
+				warn!("Couldn't serialize recipe journal: {err}");
+				return;
+			}
+		};
+
+		if let Err(err) = std::fs::write(JOURNAL_PATH, journal) {
+			warn!("Couldn't save recipe journal: {err}");
+		}
+	}
+
+	// No file system on the web :(
+	#[cfg(target_arch = "wasm32")]
+	fn load(_registry: &EffectRegistry) -> Self {
+		default()
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	fn save(&self) {}
+}
+
+#[derive(Resource, Default)]
+struct JournalView {
+	open: bool,
+}
+
+fn load_journal(mut commands: Commands, registry: Res<EffectRegistry>) {
+	commands.insert_resource(RecipeJournal::load(&registry));
+}
+
+fn save_journal(journal: Res<RecipeJournal>) {
+	if journal.is_changed() && !journal.is_added() {
+		journal.save();
+	}
+}
+
+fn toggle_journal(input: Query<&ActionState<Action>>, mut view: ResMut<JournalView>) {
+	let Ok(input) = input.get_single() else { return; };
+
+	if input.just_pressed(Action::OpenJournal) {
+		view.open = !view.open;
+	}
+}
+
+fn show_journal(
+	mut contexts: EguiContexts,
+	mut view: ResMut<JournalView>,
+	journal: Res<RecipeJournal>,
+	world_seed: Res<WorldSeed>,
+) {
+	if !view.open {
+		return;
+	}
+
+	egui::Window::new("Recipe journal")
+		.open(&mut view.open)
+		.default_width(320.0)
+		.show(contexts.ctx_mut(), |ui| {
+			if journal.0.is_empty() {
+				ui.label("Nothing brewed yet. Drink a potion to write it down.");
+				return;
+			}
+
+			egui::ScrollArea::vertical().show(ui, |ui| {
+				// Newest recipes first
+				for (i, entry) in journal.0.iter().enumerate().rev() {
+					egui::CollapsingHeader::new(format!(
+						"#{} - {} ingredients",
+						i + 1,
+						entry.ingredients.len()
+					))
+					.id_source(i)
+					.show(ui, |ui| {
+						ui.label(format!(
+							"Quality: {:.2}  Potency: {:.2}",
+							entry.quality, entry.potency
+						));
+
+						ui.label("Ingredients:");
+						for ingredient in &entry.ingredients {
+							ui.label(format!(
+								"  {}{} ({})",
+								if ingredient.is_rare { "Rare " } else { "" },
+								ingredient.name,
+								ingredient.grind.name()
+							));
+						}
+
+						ui.label("Effects:");
+						if entry.effects.is_empty() {
+							ui.label("  Nothing happened");
+						}
+						for effect in &entry.effects {
+							ui.label(format!(
+								"  {:?} ({:.0}%, {:.0}s)",
+								effect.effect,
+								effect.potency * 100.0,
+								effect.duration
+							));
+						}
+
+						if entry.world_seed != world_seed.0 {
+							ui.label("Brewed in another world, these ingredients don't grow here.");
+						}
+
+						ui.weak(entry.rebrew_hint());
+					});
+				}
+			});
+		});
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::game::effects::{EffectDef, EffectQuality};
+
+	use super::*;
+
+	fn registry() -> EffectRegistry {
+		EffectRegistry(vec![
+			EffectDef::new(EffectType("Haste"), EffectQuality::Positive),
+			EffectDef::new(EffectType("Poison"), EffectQuality::Catastrophic),
+		])
+	}
+
+	fn effect_names(journal: &RecipeJournal) -> Vec<&'static str> {
+		journal
+			.0
+			.iter()
+			.flat_map(|entry| entry.effects.iter().map(|e| e.effect.0))
+			.collect()
+	}
+
+	#[test]
+	fn journal_reads_effects_by_name() {
+		let journal = RecipeJournal(vec![JournalEntry {
+			ingredients: vec![JournalIngredient {
+				name: "Glowcap".to_string(),
+				is_rare: false,
+				grind: Grind::Fine,
+			}],
+			quality: 0.5,
+			potency: 0.5,
+			effects: vec![
+				JournalEffect {
+					effect: EffectType("Poison"),
+					potency: 0.25,
+					duration: 30.0,
+				},
+				JournalEffect {
+					effect: EffectType("Haste"),
+					potency: 0.75,
+					duration: 60.0,
+				},
+			],
+			world_seed: 7,
+		}]);
+
+		let saved = ron::to_string(&journal).unwrap();
+		let loaded = ron::from_str::<SavedJournal>(&saved)
+			.unwrap()
+			.resolve(&registry());
+
+		assert_eq!(effect_names(&loaded), ["Poison", "Haste"]);
+	}
+
+	#[test]
+	fn journal_leaves_out_unknown_effects() {
+		// Written with the old `EffectType` enum
+		let saved = "([(ingredients: [], quality: 0.5, potency: 0.5, effects: [(effect: Earthquake, potency: 0.5, duration: 30.0), (effect: Haste, potency: 0.5, duration: 30.0)], world_seed: 7)])";
+
+		let loaded = ron::from_str::<SavedJournal>(saved)
+			.unwrap()
+			.resolve(&registry());
+
+		assert_eq!(effect_names(&loaded), ["Haste"]);
+	}
+}
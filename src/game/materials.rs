@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
 use bevy::{
 	reflect::TypeUuid,
@@ -6,7 +6,7 @@ use bevy::{
 		once_cell::sync::OnceCell,
 		render_resource::{
 			AddressMode, AsBindGroup, AsBindGroupShaderType, FilterMode, SamplerDescriptor,
-			ShaderRef, ShaderType,
+			ShaderRef,
 		},
 		texture::ImageSampler,
 	},
@@ -51,7 +51,7 @@ pub fn set_dither_texture(game_assets: Res<GameAssets>, mut image_assets: ResMut
 		.unwrap();
 }
 
-#[derive(AsBindGroup, TypeUuid, Debug, Clone, Copy, Default, Reflect, FromReflect)]
+#[derive(AsBindGroup, TypeUuid, Debug, Clone, Copy, Default, PartialEq, Reflect, FromReflect)]
 #[uuid = "33fbe40a-eff7-4e20-a44f-997397cf2085"]
 #[uniform(0, FoliageMaterialUniform)]
 pub struct FoliageMaterial {
@@ -59,10 +59,18 @@ pub struct FoliageMaterial {
 	pub sss: bool,
 }
 
-#[derive(Clone, Default, ShaderType)]
-pub struct FoliageMaterialUniform {
-	pub color: Vec4,
-	pub sss: u32,
+pub use uniform::FoliageMaterialUniform;
+
+// `ShaderType` derive generates unused check functions next to the struct
+#[allow(dead_code)]
+mod uniform {
+	use bevy::{prelude::Vec4, render::render_resource::ShaderType};
+
+	#[derive(Clone, Default, ShaderType)]
+	pub struct FoliageMaterialUniform {
+		pub color: Vec4,
+		pub sss: u32,
+	}
 }
 
 impl AsBindGroupShaderType<FoliageMaterialUniform> for FoliageMaterial {
@@ -92,7 +100,7 @@ impl Material for FoliageMaterial {
 	}
 }
 
-#[derive(Clone, Reflect, FromReflect, Default, Debug)]
+#[derive(Clone, Reflect, FromReflect, Default, Debug, PartialEq)]
 pub struct NamedMaterial {
 	pub name: Cow<'static, str>,
 	pub material: FoliageMaterial,
@@ -107,7 +115,7 @@ impl NamedMaterial {
 	}
 }
 
-#[derive(Clone, Component, Default, Reflect, Debug)]
+#[derive(Clone, Component, Default, Reflect, Debug, PartialEq)]
 pub struct NamedMaterials(pub SmallVec<[NamedMaterial; 5]>);
 
 impl NamedMaterials {
@@ -115,11 +123,11 @@ impl NamedMaterials {
 		self.0.push(value)
 	}
 
-	pub fn iter(&self) -> core::slice::Iter<NamedMaterial> {
+	pub fn iter(&self) -> core::slice::Iter<'_, NamedMaterial> {
 		self.0.iter()
 	}
 
-	pub fn iter_mut(&mut self) -> core::slice::IterMut<NamedMaterial> {
+	pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, NamedMaterial> {
 		self.0.iter_mut()
 	}
 
@@ -139,9 +147,11 @@ impl NamedMaterials {
 }
 
 impl NamedMaterials {
+	/// `hue` limits the main color, used to make ingredient species recognizable
 	pub fn generate_materials(
 		archetype: SpawnableArchetype,
 		is_rare: bool,
+		hue: Option<Range<f32>>,
 		rng: &mut impl Rng,
 	) -> (Self, Color) {
 		use SpawnableArchetype::*;
@@ -181,13 +191,13 @@ impl NamedMaterials {
 
 				let berry_color = if is_rare {
 					Color::hsl(
-						rng.gen_range(0.0..360.0),
+						rng.gen_range(hue.clone().unwrap_or(0.0..360.0)),
 						rng.gen_range(0.8..1.0),
 						rng.gen_range(0.45..0.65),
 					)
 				} else {
 					Color::hsl(
-						rng.gen_range(190.0..360.0),
+						rng.gen_range(hue.unwrap_or(190.0..360.0)),
 						rng.gen_range(0.5..0.65),
 						rng.gen_range(0.35..0.55),
 					)
@@ -208,15 +218,16 @@ impl NamedMaterials {
 				])
 			}
 			Mushroom => {
+				let hue = hue.unwrap_or(0.0..360.0);
 				let cap_color = if is_rare {
 					Color::hsl(
-						rng.gen_range(0.0..360.0),
+						rng.gen_range(hue.clone()),
 						rng.gen_range(0.8..1.0),
 						rng.gen_range(0.45..0.65),
 					)
 				} else {
 					Color::hsl(
-						rng.gen_range(0.0..360.0),
+						rng.gen_range(hue),
 						rng.gen_range(0.5..0.65),
 						rng.gen_range(0.35..0.55),
 					)
@@ -235,6 +246,73 @@ impl NamedMaterials {
 					NamedMaterial::new("Stem", stem_color),
 				])
 			}
+			Root => {
+				let hue = hue.unwrap_or(10.0..60.0);
+				let root_color = if is_rare {
+					Color::hsl(
+						rng.gen_range(hue),
+						rng.gen_range(0.8..1.0),
+						rng.gen_range(0.45..0.65),
+					)
+				} else {
+					Color::hsl(
+						rng.gen_range(hue),
+						rng.gen_range(0.4..0.6),
+						rng.gen_range(0.3..0.45),
+					)
+				};
+
+				main_color = root_color;
+
+				NamedMaterials(smallvec![
+					NamedMaterial::new("Root", root_color),
+					NamedMaterial {
+						name: Cow::Borrowed("Leaves"),
+						material: FoliageMaterial {
+							color: *choice![Color::LIME_GREEN, Color::YELLOW_GREEN].random(rng),
+							sss: true
+						}
+					},
+					NamedMaterial::new("Hole", Color::rgb(0.12, 0.06, 0.0)),
+					NamedMaterial::new("Dirt", Color::rgb(0.35, 0.18, 0.02)),
+				])
+			}
+			Plant => {
+				let hue = hue.unwrap_or(0.0..360.0);
+				let flower_color = if is_rare {
+					Color::hsl(
+						rng.gen_range(hue),
+						rng.gen_range(0.8..1.0),
+						rng.gen_range(0.55..0.75),
+					)
+				} else {
+					Color::hsl(
+						rng.gen_range(hue),
+						rng.gen_range(0.5..0.7),
+						rng.gen_range(0.45..0.6),
+					)
+				};
+
+				let stem_color = if is_rare {
+					Color::rgb(0.6, 0.9, 0.7)
+				} else {
+					Color::rgb(0.3, 0.55, 0.1)
+				};
+
+				main_color = flower_color;
+
+				NamedMaterials(smallvec![
+					NamedMaterial::new("Flower", flower_color),
+					NamedMaterial::new("Stem", stem_color),
+					NamedMaterial {
+						name: Cow::Borrowed("Leaves"),
+						material: FoliageMaterial {
+							color: stem_color,
+							sss: true
+						}
+					},
+				])
+			}
 		};
 
 		(named_materials, main_color)
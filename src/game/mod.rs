@@ -3,14 +3,23 @@ use crate::prelude::*;
 use self::ingredient::Ingredient;
 
 pub mod alchemy;
+pub mod appraisal;
 pub mod backpack;
+pub mod biome;
 pub mod effects;
+pub mod hallucinations;
+pub mod hud;
 pub mod ingredient;
 pub mod input;
 pub mod items;
+pub mod journal;
 pub mod materials;
 pub mod physics;
 pub mod player;
+pub mod regrowth;
+pub mod splash;
+pub mod thief;
+pub mod vitals;
 pub mod world;
 
 pub struct GamePlugin;
@@ -26,6 +35,14 @@ impl Plugin for GamePlugin {
 			.add_plugin(alchemy::AlchemyPlugin)
 			.add_plugin(items::ItemsPlugin)
 			.add_plugin(effects::EffectsPlugin)
+			.add_plugin(journal::JournalPlugin)
+			.add_plugin(appraisal::AppraisalPlugin)
+			.add_plugin(splash::SplashPlugin)
+			.add_plugin(regrowth::RegrowthPlugin)
+			.add_plugin(thief::ThiefPlugin)
+			.add_plugin(hallucinations::HallucinationsPlugin)
+			.add_plugin(vitals::VitalsPlugin)
+			.add_plugin(hud::HudPlugin)
 			.register_type::<Ingredient>();
 	}
 }
@@ -1,8 +1,13 @@
-use crate::{assets::SHADOW_BUNDLE, game::effects::EffectType, prelude::*};
+use crate::{assets::SHADOW_BUNDLE, prelude::*};
 use bevy::{core_pipeline::fxaa::Fxaa, gltf::Gltf, math::Vec3Swizzles};
 use bevy_inspector_egui::egui::lerp;
 
-use super::{backpack::Inventory, effects::ActiveEffects, world::Shadow};
+use super::{
+	backpack::Inventory,
+	effects::{ActiveEffects, EffectAppExt, EffectDef, EffectQuality, EffectType, StackRule},
+	vitals::{Fainted, Vitals},
+	world::Shadow,
+};
 
 pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
@@ -13,10 +18,27 @@ impl Plugin for PlayerPlugin {
 					.chain()
 					.in_set(OnUpdate(GameState::InGame)),
 			)
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::SLOWNESS),
+				color: Color::rgb(0.3, 0.9, 1.0),
+				..EffectDef::new(EffectType::HASTE, EffectQuality::Positive)
+			})
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::HASTE),
+				color: Color::rgb(0.2, 0.3, 0.6),
+				..EffectDef::new(EffectType::SLOWNESS, EffectQuality::Negative)
+			})
 			.register_type::<Inventory>();
 	}
 }
 
+impl EffectType {
+	pub const HASTE: EffectType = EffectType("Haste");
+	pub const SLOWNESS: EffectType = EffectType("Slowness");
+}
+
 #[derive(Component, Clone, Copy, Debug)]
 pub struct Player;
 
@@ -33,6 +55,7 @@ pub fn spawn_player(
 		.spawn((
 			Name::new("Player"),
 			Player,
+			Vitals::default(),
 			Inventory::default(),
 			SceneBundle {
 				scene: game_assets.player_scene.clone(),
@@ -106,6 +129,8 @@ pub fn spawn_player(
 			..default()
 		},
 		Fxaa::default(),
+		// UI is drawn on top of the inventory camera instead
+		UiCameraConfig { show_ui: false },
 	));
 }
 
@@ -124,7 +149,7 @@ pub fn move_player(
 			&ActionState<Action>,
 			&mut Transform,
 		),
-		With<Player>,
+		(With<Player>, Without<Fainted>),
 	>,
 	cam_query: Query<&Transform, (With<PlayerCamera>, Without<Player>)>,
 	mut desired_rotation: Local<Quat>,
@@ -171,11 +196,11 @@ pub fn move_player(
 	let existing_translation = controller.translation.unwrap_or_default();
 
 	let speed_modifiers = effects
-		.has_effect(EffectType::Haste)
+		.has_effect(EffectType::HASTE)
 		.map(|a| lerp(1.2..=2.0, a.potency))
 		.unwrap_or(1.0)
 		* effects
-			.has_effect(EffectType::Slowness)
+			.has_effect(EffectType::SLOWNESS)
 			.map(|a| lerp(0.9..=0.6, a.potency))
 			.unwrap_or(1.0);
 
@@ -0,0 +1,286 @@
+use std::ops::Range;
+
+use bevy::math::Vec3Swizzles;
+
+use crate::{
+	assets::{PickUpEvent, SceneInstanceReady, Spawnable},
+	prelude::*,
+};
+
+use super::{
+	biome::BiomeMap,
+	ingredient::{Ingredient, SpeciesRegistry},
+	world::{
+		spawn_spawnable_instance, OccupiedSpawnSpace, SpawnPlan, SpawnSpace, SpawnableInstance,
+		VegetationCollection, ISLAND_SIZE,
+	},
+};
+
+pub struct RegrowthPlugin;
+impl Plugin for RegrowthPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<RegrowthSettings>()
+			.init_resource::<PendingRespawns>()
+			.add_event::<HarvestEvent>()
+			.add_systems(
+				(
+					start_regrowth,
+					regrow_harvested.after(start_regrowth),
+					respawn_destroyed.after(start_regrowth),
+				)
+					.in_set(OnUpdate(GameState::InGame)),
+			);
+	}
+}
+
+/// How fast the island grows back
+#[derive(Resource, Debug, Clone)]
+pub struct RegrowthSettings {
+	/// Seconds until picked berries and flowers grow back
+	pub berry_time: Range<f32>,
+	/// Seconds until a root grows back in its hole
+	pub root_time: Range<f32>,
+	/// Seconds until a picked mushroom (or anything that vanished) grows back somewhere nearby
+	pub mushroom_time: Range<f32>,
+	/// How far from the old spot the new mushroom can grow
+	pub mushroom_distance: f32,
+}
+
+impl Default for RegrowthSettings {
+	fn default() -> Self {
+		RegrowthSettings {
+			berry_time: 60.0..120.0,
+			root_time: 120.0..240.0,
+			mushroom_time: 90.0..240.0,
+			mushroom_distance: 6.0,
+		}
+	}
+}
+
+/// Sent when the player picks an ingredient growing in the world
+pub struct HarvestEvent {
+	pub entity: Entity,
+	pub spawnable: Handle<Spawnable>,
+	pub position: Vec3,
+	/// Whatever grows back, is of the same species
+	pub species: usize,
+	/// Whole spawnable is gone (hallucinated away), so it can't grow back in place
+	pub vanished: bool,
+}
+
+/// Spawnable, that was picked clean and waits for its ingredient to grow back
+#[derive(Component, Debug)]
+pub struct Regrowing {
+	pub time_left: f32,
+	pub species: usize,
+	/// How it was picked, so it can be undone
+	pick_event: PickUpEvent,
+}
+
+/// Destroyed spawnables, that will grow back somewhere near
+#[derive(Resource, Default)]
+struct PendingRespawns(Vec<PendingRespawn>);
+
+struct PendingRespawn {
+	spawnable: Handle<Spawnable>,
+	position: Vec2,
+	species: usize,
+	time_left: f32,
+}
+
+fn start_regrowth(
+	mut commands: Commands,
+	mut harvest_events: EventReader<HarvestEvent>,
+	mut pending: ResMut<PendingRespawns>,
+	mut occupied_space: ResMut<OccupiedSpawnSpace>,
+	spawnables: Res<Assets<Spawnable>>,
+	settings: Res<RegrowthSettings>,
+) {
+	let mut rng = thread_rng();
+
+	for event in harvest_events.iter() {
+		let Some(spawnable) = spawnables.get(&event.spawnable) else { continue; };
+		let Some(ingredient) = &spawnable.ingredient else { continue; };
+
+		match &ingredient.pick_event {
+			PickUpEvent::RemoveNamedChild(_) | PickUpEvent::Replace(_) if !event.vanished => {
+				let Some(mut entity) = commands.get_entity(event.entity) else { continue; };
+
+				let time = if ingredient.dig {
+					settings.root_time.clone()
+				} else {
+					settings.berry_time.clone()
+				};
+
+				entity.insert(Regrowing {
+					time_left: rng.gen_range(time),
+					species: event.species,
+					pick_event: ingredient.pick_event.clone(),
+				});
+			}
+			// Destroyed or vanished, grows back somewhere near
+			_ => {
+				let position = event.position.xz();
+				occupied_space.remove(position);
+
+				pending.0.push(PendingRespawn {
+					spawnable: event.spawnable.clone(),
+					position,
+					species: event.species,
+					time_left: rng.gen_range(settings.mushroom_time.clone()),
+				});
+			}
+		}
+	}
+}
+
+/// Undoes the picking, and gives the spawnable a new ingredient
+fn regrow_harvested(
+	mut commands: Commands,
+	mut regrowing_query: Query<(
+		Entity,
+		&mut Regrowing,
+		&SpawnableInstance,
+		&mut NamedMaterials,
+	)>,
+	name_query: Query<&Name>,
+	children_query: Query<&Children>,
+	spawnables: Res<Assets<Spawnable>>,
+	species_registry: Res<SpeciesRegistry>,
+	time: Res<Time>,
+) {
+	let mut rng = thread_rng();
+
+	for (entity, mut regrowing, instance, mut named_materials) in &mut regrowing_query {
+		regrowing.time_left -= time.delta_seconds();
+		if regrowing.time_left > 0.0 {
+			continue;
+		}
+
+		commands.entity(entity).remove::<Regrowing>();
+
+		let Some(spawnable) = spawnables.get(&instance.handle) else { continue; };
+		let Some(species) = species_registry.get(regrowing.species) else { continue; };
+
+		let (new_materials, color) = NamedMaterials::generate_materials(
+			spawnable.archetype,
+			instance.rare,
+			Some(species.hue.clone()),
+			&mut rng,
+		);
+
+		match &regrowing.pick_event {
+			PickUpEvent::RemoveNamedChild(child_name) => {
+				// Fresh color for what grew back, the rest of the spawnable stays as it was
+				let new_material = new_materials.iter().find(|m| m.name == *child_name);
+				let material = named_materials.iter_mut().find(|m| m.name == *child_name);
+				if let (Some(new_material), Some(material)) = (new_material, material) {
+					material.material = new_material.material;
+				}
+
+				for child in children_query.iter_descendants(entity) {
+					let Ok(name) = name_query.get(child) else { continue; };
+
+					if name.contains(child_name) {
+						commands.entity(child).insert(Visibility::Inherited);
+					}
+				}
+			}
+			PickUpEvent::Replace(_) => {
+				// Grew back whole
+				*named_materials = new_materials;
+				commands
+					.entity(entity)
+					.remove::<(Handle<Scene>, SceneInstanceReady)>()
+					.insert(spawnable.scene.clone());
+			}
+			PickUpEvent::Destroy => {}
+		}
+
+		commands.entity(entity).insert(Ingredient::from_species(
+			regrowing.species,
+			species,
+			instance.rare,
+			color,
+			instance.size,
+		));
+	}
+}
+
+/// Destroyed spawnables grow back close to where they were
+fn respawn_destroyed(
+	mut commands: Commands,
+	mut pending: ResMut<PendingRespawns>,
+	mut occupied_space: ResMut<OccupiedSpawnSpace>,
+	collection_query: Query<Entity, With<VegetationCollection>>,
+	spawnables: Res<Assets<Spawnable>>,
+	species_registry: Res<SpeciesRegistry>,
+	biome_map: Res<BiomeMap>,
+	settings: Res<RegrowthSettings>,
+	time: Res<Time>,
+) {
+	let mut rng = thread_rng();
+
+	for respawn in &mut pending.0 {
+		respawn.time_left -= time.delta_seconds();
+	}
+
+	let (ready, still_pending): (Vec<_>, Vec<_>) = std::mem::take(&mut pending.0)
+		.into_iter()
+		.partition(|r| r.time_left <= 0.0);
+	pending.0 = still_pending;
+
+	for mut respawn in ready {
+		let Some(spawnable) = spawnables.get(&respawn.spawnable) else { continue; };
+		let Some(species) = species_registry.get(respawn.species) else { continue; };
+
+		let biome = biome_map.biome_at(respawn.position);
+		let is_rare = rng.gen_bool((biome.rare_chance() / 200.0) as f64);
+		let relative_scale = if is_rare {
+			rng.gen_range(1.35..1.8)
+		} else {
+			rng.gen_range(0.7..1.35)
+		} * species.base_size;
+		let size = spawnable.size * relative_scale;
+
+		let position = (0..10).find_map(|_| {
+			let offset = Vec2::from_angle(rng.gen_range(-PI..PI))
+				* rng.gen_range(0.5..settings.mushroom_distance);
+			let position = respawn.position + offset;
+
+			let free = position.length() < ISLAND_SIZE - 5.0
+				&& position.length_squared() > 12.0
+				&& !occupied_space.is_occupied(position, size);
+
+			free.then_some(position)
+		});
+
+		// Everything around is full, try again later
+		let Some(position) = position else {
+			respawn.time_left = rng.gen_range(settings.mushroom_time.clone()) * 0.25;
+			pending.0.push(respawn);
+			continue;
+		};
+
+		occupied_space.insert(position, size);
+
+		let plan = SpawnPlan::new(
+			spawnable,
+			position,
+			is_rare,
+			relative_scale,
+			Some((respawn.species, species)),
+			&mut rng,
+		);
+		let entity = spawn_spawnable_instance(
+			&mut commands,
+			spawnable,
+			respawn.spawnable.clone(),
+			plan,
+			&species_registry,
+		);
+		if let Ok(collection) = collection_query.get_single() {
+			commands.entity(collection).add_child(entity);
+		}
+	}
+}
@@ -0,0 +1,238 @@
+use bevy_inspector_egui::egui::lerp;
+
+use crate::{assets::Spawnable, prelude::*};
+
+use super::{
+	effects::{ActiveEffects, EffectQuality, EffectRegistry},
+	hallucinations::Phantom,
+	ingredient::{Ingredient, SpeciesRegistry},
+	items::{DroppedItem, Item, Potion},
+	journal::{JournalEntry, RecipeJournal},
+	player::Player,
+	world::{SpawnableInstance, WorldSeed},
+};
+
+pub struct SplashPlugin;
+impl Plugin for SplashPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			(shatter_splash_potions, animate_splashes).in_set(OnUpdate(GameState::InGame)),
+		);
+	}
+}
+
+/// Filled potion, that was thrown out of the backpack. Shatters on contact.
+#[derive(Component)]
+pub struct SplashPotion;
+
+/// Visual of a shattered potion
+#[derive(Component)]
+struct Splash {
+	age: f32,
+	material: Handle<StandardMaterial>,
+}
+
+const SPLASH_RADIUS: f32 = 4.0;
+const SPLASH_DURATION: f32 = 0.4;
+
+fn shatter_splash_potions(
+	mut commands: Commands,
+	mut collision_events: EventReader<CollisionEvent>,
+	potion_query: Query<(Entity, &Transform, &Item), With<SplashPotion>>,
+	player_query: Query<&Transform, (With<Player>, Without<SplashPotion>)>,
+	mut spawnable_query: Query<
+		(
+			&mut Transform,
+			&mut Ingredient,
+			&mut SpawnableInstance,
+			&mut NamedMaterials,
+		),
+		(
+			Without<DroppedItem>,
+			Without<Phantom>,
+			Without<Player>,
+			Without<SplashPotion>,
+		),
+	>,
+	mut dropped_query: Query<
+		(&Transform, &mut Velocity, Option<&mut Ingredient>),
+		(With<DroppedItem>, Without<SplashPotion>),
+	>,
+	mut active_effects: ResMut<ActiveEffects>,
+	effect_registry: Res<EffectRegistry>,
+	(mut journal, world_seed): (ResMut<RecipeJournal>, Res<WorldSeed>),
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut standard_mat: ResMut<Assets<StandardMaterial>>,
+	spawnables: Res<Assets<Spawnable>>,
+	species_registry: Res<SpeciesRegistry>,
+	game_assets: Res<GameAssets>,
+	sound: Res<Audio>,
+	mut splash_mesh: Local<Option<Handle<Mesh>>>,
+) {
+	let mut shattered: SmallVec<[Entity; 4]> = smallvec![];
+
+	for event in collision_events.iter() {
+		let CollisionEvent::Started(a, b, _) = event else { continue; };
+
+		let Some(potion) = [*a, *b].into_iter().find(|e| potion_query.contains(*e)) else {
+			continue;
+		};
+
+		if !shattered.contains(&potion) {
+			shattered.push(potion);
+		}
+	}
+
+	// Thrown off the island, nothing to shatter on
+	for (entity, transform, _) in &potion_query {
+		if transform.translation.y < -30.0 {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+
+	let mut rng = thread_rng();
+
+	for (entity, potion_transform, item) in shattered
+		.into_iter()
+		.filter_map(|e| potion_query.get(e).ok())
+	{
+		commands.entity(entity).despawn_recursive();
+		sound.play(game_assets.ting_sound.clone());
+
+		let Item::Potion(potion) = item else { continue; };
+		let Potion::Filled { ingridients, color, .. } = potion else { continue; };
+		let Some((quality, potency, effects)) = potion.brew(&effect_registry) else { continue; };
+
+		let center = potion_transform.translation;
+
+		// Splash visual
+		let material = standard_mat.add(StandardMaterial {
+			base_color: color.with_a(0.6),
+			alpha_mode: AlphaMode::Blend,
+			unlit: true,
+			..default()
+		});
+
+		commands.spawn((
+			Name::new("Splash"),
+			Splash {
+				age: 0.0,
+				material: material.clone(),
+			},
+			PbrBundle {
+				mesh: splash_mesh
+					.get_or_insert_with(|| {
+						meshes.add(Mesh::from(shape::UVSphere {
+							radius: 1.0,
+							..default()
+						}))
+					})
+					.clone(),
+				material,
+				transform: Transform::from_translation(center).with_scale(Vec3::splat(0.1)),
+				..default()
+			},
+		));
+
+		// Good potions bless everything around, bad ones make it wither
+		let blessing = effects
+			.iter()
+			.map(|e| match effect_registry.quality(e.effect) {
+				EffectQuality::Exceptional | EffectQuality::Positive => 1,
+				EffectQuality::Neutral => 0,
+				EffectQuality::Negative | EffectQuality::Catastrophic => -1,
+			})
+			.sum::<i32>();
+
+		// Player
+		if let Ok(player_transform) = player_query.get_single() {
+			if player_transform.translation.distance_squared(center) < SPLASH_RADIUS.powi(2) {
+				journal.0.push(JournalEntry::new(
+					ingridients,
+					quality,
+					potency,
+					&effects,
+					*world_seed,
+				));
+
+				for effect in &effects {
+					active_effects.push(*effect, &effect_registry);
+				}
+			}
+		}
+
+		// Ingredients growing in the world
+		for (mut transform, mut ingredient, mut instance, mut materials) in &mut spawnable_query {
+			if transform.translation.distance_squared(center) > SPLASH_RADIUS.powi(2) {
+				continue;
+			}
+
+			if blessing > 0 && !instance.rare {
+				let Some(spawnable) = spawnables.get(&instance.handle) else { continue; };
+
+				let (new_materials, color) = NamedMaterials::generate_materials(
+					spawnable.archetype,
+					true,
+					species_registry
+						.get(ingredient.species)
+						.map(|s| s.hue.clone()),
+					&mut rng,
+				);
+
+				*materials = new_materials;
+				ingredient.color = color;
+				ingredient.is_rare = true;
+				instance.rare = true;
+				transform.scale *= 1.3;
+			} else if blessing < 0 {
+				ingredient.is_rare = false;
+				ingredient.size *= 0.7;
+				instance.size *= 0.7;
+				transform.scale *= 0.7;
+			}
+		}
+
+		// Items lying around
+		for (transform, mut velocity, ingredient) in &mut dropped_query {
+			let relative = transform.translation - center;
+
+			if relative.length_squared() > SPLASH_RADIUS.powi(2) {
+				continue;
+			}
+
+			velocity.linvel += relative.normalize_or_zero() * 6.0 + Vec3::Y * 4.0;
+
+			if let Some(mut ingredient) = ingredient {
+				if blessing > 0 {
+					ingredient.is_rare = true;
+				} else if blessing < 0 {
+					ingredient.is_rare = false;
+					ingredient.size *= 0.7;
+				}
+			}
+		}
+	}
+}
+
+fn animate_splashes(
+	mut commands: Commands,
+	mut splash_query: Query<(Entity, &mut Splash, &mut Transform)>,
+	mut standard_mat: ResMut<Assets<StandardMaterial>>,
+	time: Res<Time>,
+) {
+	for (entity, mut splash, mut transform) in &mut splash_query {
+		splash.age += time.delta_seconds();
+
+		let t = (splash.age / SPLASH_DURATION).min(1.0);
+
+		transform.scale = Vec3::splat(lerp(0.1..=SPLASH_RADIUS, t.sqrt()));
+
+		if let Some(material) = standard_mat.get_mut(&splash.material) {
+			material.base_color.set_a(lerp(0.6..=0.0, t));
+		}
+
+		if t >= 1.0 {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+}
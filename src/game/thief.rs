@@ -0,0 +1,304 @@
+use bevy::{math::Vec3Swizzles, render::view::RenderLayers};
+use bevy_inspector_egui::egui::lerp;
+
+use crate::prelude::*;
+
+use super::{
+	alchemy::{Cauldroned, Eaten},
+	effects::{
+		ActiveEffects, EffectAppExt, EffectDef, EffectQuality, EffectStage, EffectType, StackRule,
+	},
+	items::{drop_item, DroppedItem, Grabber, Item, ItemSize},
+	player::Player,
+	world::ISLAND_SIZE,
+};
+
+pub struct ThiefPlugin;
+impl Plugin for ThiefPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_effect(EffectDef {
+			stack_rule: StackRule::CancelOpposite,
+			opposite: Some(EffectType::LUCKY_HANDS),
+			color: Color::rgb(0.15, 0.1, 0.2),
+			..EffectDef::new(EffectType::THIEF, EffectQuality::Negative)
+		})
+		.add_startup_system(create_thief_assets)
+		.add_effect_system(EffectType::THIEF, EffectStage::Tick, spawn_thief)
+		.add_effect_system(
+			EffectType::THIEF,
+			EffectStage::Tick,
+			steal_ingredient.after(move_thief),
+		)
+		.add_systems(
+			(
+				move_thief.after(spawn_thief),
+				release_stolen.after(steal_ingredient),
+				carry_stolen.after(release_stolen),
+			)
+				.in_set(OnUpdate(GameState::InGame)),
+		);
+	}
+}
+
+impl EffectType {
+	pub const THIEF: EffectType = EffectType("Thief");
+}
+
+/// Little creature, that follows the player around and steals from the backpack
+#[derive(Component, Default)]
+pub struct Thief {
+	/// Item, that is being carried away
+	pub stolen: Option<Entity>,
+	/// Seconds until the next theft
+	pub cooldown: f32,
+}
+
+/// Item carried by a thief
+#[derive(Component)]
+pub struct Stolen;
+
+/// No model for the thief, so it's built from these
+#[derive(Resource)]
+struct ThiefAssets {
+	body_mesh: Handle<Mesh>,
+	body_material: Handle<StandardMaterial>,
+	eye_mesh: Handle<Mesh>,
+	eye_material: Handle<StandardMaterial>,
+}
+
+const THIEF_SPEED: f32 = 6.5;
+/// Player has to get this close to make the thief drop what it stole
+const CATCH_DISTANCE: f32 = 1.5;
+
+fn create_thief_assets(
+	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut standard_mat: ResMut<Assets<StandardMaterial>>,
+) {
+	commands.insert_resource(ThiefAssets {
+		body_mesh: meshes.add(Mesh::from(shape::Capsule {
+			radius: 0.3,
+			depth: 0.3,
+			..default()
+		})),
+		body_material: standard_mat.add(StandardMaterial {
+			base_color: Color::rgb(0.1, 0.08, 0.15),
+			perceptual_roughness: 0.9,
+			..default()
+		}),
+		eye_mesh: meshes.add(Mesh::from(shape::UVSphere {
+			radius: 0.07,
+			..default()
+		})),
+		eye_material: standard_mat.add(StandardMaterial {
+			base_color: Color::YELLOW,
+			unlit: true,
+			..default()
+		}),
+	});
+}
+
+fn spawn_thief(
+	mut commands: Commands,
+	thief_assets: Res<ThiefAssets>,
+	thief_query: Query<(), With<Thief>>,
+	player_query: Query<&Transform, With<Player>>,
+) {
+	if !thief_query.is_empty() {
+		return;
+	}
+
+	let Ok(player_transform) = player_query.get_single() else { return; };
+
+	let mut rng = thread_rng();
+	let offset = Quat::from_rotation_y(rng.gen_range(-PI..PI)) * Vec3::Z * 8.0;
+
+	// No model for it, so it's a little shadow blob with glowing eyes
+	commands
+		.spawn((
+			Name::new("Thief"),
+			Thief {
+				cooldown: 5.0,
+				..default()
+			},
+			PbrBundle {
+				mesh: thief_assets.body_mesh.clone(),
+				material: thief_assets.body_material.clone(),
+				transform: Transform::from_translation(
+					(player_transform.translation + offset) * Vec3::new(1.0, 0.0, 1.0)
+						+ Vec3::Y * 0.45,
+				),
+				..default()
+			},
+			RenderLayers::layer(0),
+		))
+		.with_children(|commands| {
+			for x in [-0.12, 0.12] {
+				commands.spawn(PbrBundle {
+					mesh: thief_assets.eye_mesh.clone(),
+					material: thief_assets.eye_material.clone(),
+					transform: Transform::from_xyz(x, 0.2, 0.27),
+					..default()
+				});
+			}
+		});
+}
+
+/// Follows the player, or runs away with the loot
+fn move_thief(
+	mut thief_query: Query<(&mut Transform, &mut Thief), Without<Player>>,
+	player_query: Query<&Transform, With<Player>>,
+	time: Res<Time>,
+) {
+	let Ok(player_transform) = player_query.get_single() else { return; };
+
+	for (mut transform, mut thief) in &mut thief_query {
+		thief.cooldown -= time.delta_seconds();
+
+		let to_player = (player_transform.translation - transform.translation).xz();
+		let distance = to_player.length();
+
+		let direction = if thief.stolen.is_some() {
+			-to_player.normalize_or_zero()
+		} else if distance > 2.5 {
+			to_player.normalize_or_zero()
+		} else {
+			Vec2::ZERO
+		};
+
+		// Catches up quickly, but can be outrun when fleeing
+		let speed = if thief.stolen.is_some() {
+			THIEF_SPEED
+		} else {
+			THIEF_SPEED * (distance / 4.0).clamp(1.0, 3.0)
+		};
+
+		transform.translation += direction.extend(0.0).xzy() * speed * time.delta_seconds();
+
+		// Doesn't flee off the island
+		let position = transform
+			.translation
+			.xz()
+			.clamp_length_max(ISLAND_SIZE - 5.0);
+		transform.translation = position.extend(transform.translation.y).xzy();
+
+		if direction != Vec2::ZERO {
+			let rotation = Quat::from_rotation_arc(Vec3::Z, direction.extend(0.0).xzy());
+			transform.rotation = Quat::slerp(
+				transform.rotation,
+				rotation,
+				1.0 - 0.001f32.powf(time.delta_seconds()),
+			);
+		}
+	}
+}
+
+fn steal_ingredient(
+	mut commands: Commands,
+	mut thief_query: Query<(&Transform, &mut Thief)>,
+	mut item_query: Query<
+		(Entity, &mut Transform, &Item, &mut ItemSize, &mut Velocity),
+		(
+			Without<DroppedItem>,
+			Without<Eaten>,
+			Without<Cauldroned>,
+			Without<Thief>,
+		),
+	>,
+	mut grabber_query: Query<&mut Grabber>,
+	active_effects: Res<ActiveEffects>,
+	game_assets: Res<GameAssets>,
+	sound: Res<Audio>,
+) {
+	let Some(thief_effect) = active_effects.has_effect(EffectType::THIEF) else { return; };
+
+	let mut rng = thread_rng();
+
+	for (thief_transform, mut thief) in &mut thief_query {
+		if thief.stolen.is_some() || thief.cooldown > 0.0 {
+			continue;
+		}
+
+		thief.cooldown = lerp(15.0..=6.0, thief_effect.potency) * rng.gen_range(0.8..1.2);
+
+		let Some((entity, mut transform, _, mut item_size, mut velocity)) = item_query
+			.iter_mut()
+			.filter(|(_, _, item, _, _)| matches!(item, Item::Ingredient))
+			.choose(&mut rng)
+		else {
+			continue;
+		};
+
+		if let Ok(mut grabber) = grabber_query.get_single_mut() {
+			if grabber.grabbed_entity == Some(entity) {
+				grabber.ungrab = true;
+			}
+		}
+
+		drop_item(
+			&mut commands,
+			entity,
+			&mut transform,
+			&mut item_size,
+			&mut velocity,
+			thief_transform.translation,
+		);
+
+		commands
+			.entity(entity)
+			.insert((Stolen, ColliderDisabled, GravityScale(0.0)));
+
+		thief.stolen = Some(entity);
+
+		sound.play(game_assets.wha_sound.clone());
+	}
+}
+
+/// Thief drops the loot when caught, and leaves when the effect is over
+fn release_stolen(
+	mut commands: Commands,
+	mut thief_query: Query<(Entity, &Transform, &mut Thief)>,
+	mut stolen_query: Query<&mut Velocity, With<Stolen>>,
+	player_query: Query<&Transform, With<Player>>,
+	active_effects: Res<ActiveEffects>,
+) {
+	let Ok(player_transform) = player_query.get_single() else { return; };
+	let effect_over = active_effects.has_effect(EffectType::THIEF).is_none();
+
+	for (entity, transform, mut thief) in &mut thief_query {
+		let distance = transform
+			.translation
+			.xz()
+			.distance(player_transform.translation.xz());
+		let caught = distance < CATCH_DISTANCE;
+
+		if caught || effect_over {
+			if let Some(stolen) = thief.stolen.take() {
+				commands
+					.entity(stolen)
+					.remove::<(Stolen, ColliderDisabled, GravityScale)>();
+
+				if let Ok(mut velocity) = stolen_query.get_mut(stolen) {
+					velocity.linvel = Vec3::Y * 6.0;
+				}
+			}
+		}
+
+		if effect_over {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+}
+
+fn carry_stolen(
+	thief_query: Query<(&Transform, &Thief)>,
+	mut stolen_query: Query<(&mut Transform, &mut Velocity), (With<Stolen>, Without<Thief>)>,
+) {
+	for (thief_transform, thief) in &thief_query {
+		let Some(stolen) = thief.stolen else { continue; };
+		let Ok((mut transform, mut velocity)) = stolen_query.get_mut(stolen) else { continue; };
+
+		transform.translation = thief_transform.translation + Vec3::Y * 0.8;
+		*velocity = Velocity::zero();
+	}
+}
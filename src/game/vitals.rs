@@ -0,0 +1,198 @@
+use bevy_inspector_egui::{
+	bevy_egui::{egui, EguiContexts},
+	egui::lerp,
+};
+
+use crate::prelude::*;
+
+use super::{
+	effects::{
+		ActiveEffects, EffectAppExt, EffectDef, EffectQuality, EffectStage, EffectType, StackRule,
+	},
+	player::Player,
+};
+
+pub struct VitalsPlugin;
+impl Plugin for VitalsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<DamageEvent>()
+			.add_systems(
+				(
+					fall_damage,
+					apply_damage.after(fall_damage).after(poison_damage),
+					respawn_fainted.after(apply_damage),
+					show_vitals,
+				)
+					.in_set(OnUpdate(GameState::InGame)),
+			)
+			.add_effect(EffectDef {
+				stack_rule: StackRule::CancelOpposite,
+				opposite: Some(EffectType::EARTHQUAKE),
+				color: Color::GOLD,
+				..EffectDef::new(EffectType::GOD_MODE, EffectQuality::Exceptional)
+			})
+			.add_effect(EffectDef {
+				stack_rule: StackRule::StackPotency,
+				color: Color::rgb(0.5, 0.8, 0.1),
+				..EffectDef::new(EffectType::POISON, EffectQuality::Catastrophic)
+			})
+			.add_effect_system(EffectType::POISON, EffectStage::Tick, poison_damage)
+			.register_type::<Vitals>();
+	}
+}
+
+impl EffectType {
+	pub const GOD_MODE: EffectType = EffectType("GodMode");
+	pub const POISON: EffectType = EffectType("Poison");
+}
+
+/// Where the player wakes up after falling off or fainting
+pub const RESPAWN_POINT: Vec3 = Vec3::new(0.0, 2.0, 0.0);
+
+/// How many seconds the player stays down after fainting
+const FAINT_TIME: f32 = 3.0;
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+pub struct Vitals {
+	pub health: f32,
+	pub max_health: f32,
+}
+
+impl Default for Vitals {
+	fn default() -> Self {
+		Self {
+			health: 100.0,
+			max_health: 100.0,
+		}
+	}
+}
+
+/// Player ran out of health. Can't move until respawned.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Fainted(pub f32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+	Fall,
+	Poison,
+	PoisonousIngredient,
+}
+
+/// Hurts the player, unless GodMode is active
+#[derive(Debug, Clone, Copy)]
+pub struct DamageEvent {
+	pub amount: f32,
+	pub source: DamageSource,
+}
+
+fn fall_damage(
+	mut player_query: Query<&mut Transform, With<Player>>,
+	mut damage_events: EventWriter<DamageEvent>,
+) {
+	let Ok(mut transform) = player_query.get_single_mut() else { return; };
+
+	if transform.translation.y > -20.0 {
+		return;
+	}
+
+	transform.translation = RESPAWN_POINT;
+
+	damage_events.send(DamageEvent {
+		amount: 40.0,
+		source: DamageSource::Fall,
+	});
+}
+
+fn poison_damage(
+	active_effects: Res<ActiveEffects>,
+	mut damage_events: EventWriter<DamageEvent>,
+	time: Res<Time>,
+) {
+	let Some(poison) = active_effects.has_effect(EffectType::POISON) else { return; };
+
+	damage_events.send(DamageEvent {
+		amount: lerp(2.0..=8.0, poison.potency) * time.delta_seconds(),
+		source: DamageSource::Poison,
+	});
+}
+
+fn apply_damage(
+	mut commands: Commands,
+	mut player_query: Query<(Entity, &mut Vitals), (With<Player>, Without<Fainted>)>,
+	mut damage_events: EventReader<DamageEvent>,
+	active_effects: Res<ActiveEffects>,
+	game_assets: Res<GameAssets>,
+	sound: Res<Audio>,
+) {
+	let Ok((player, mut vitals)) = player_query.get_single_mut() else {
+		damage_events.clear();
+		return;
+	};
+
+	// Nothing can hurt a god
+	if active_effects.has_effect(EffectType::GOD_MODE).is_some() {
+		damage_events.clear();
+		return;
+	}
+
+	for event in damage_events.iter() {
+		vitals.health -= event.amount;
+
+		// Poison ticks every frame, don't spam the sound
+		if event.source != DamageSource::Poison {
+			sound.play(game_assets.oh_no_sound.clone());
+		}
+	}
+
+	if vitals.health <= 0.0 {
+		vitals.health = 0.0;
+		commands.entity(player).insert(Fainted(0.0));
+	}
+}
+
+fn respawn_fainted(
+	mut commands: Commands,
+	mut player_query: Query<(Entity, &mut Transform, &mut Vitals, &mut Fainted), With<Player>>,
+	mut active_effects: ResMut<ActiveEffects>,
+	time: Res<Time>,
+) {
+	let Ok((player, mut transform, mut vitals, mut fainted)) = player_query.get_single_mut() else { return; };
+
+	fainted.0 += time.delta_seconds();
+
+	if fainted.0 < FAINT_TIME {
+		return;
+	}
+
+	// Wake up fresh, with a clear head
+	transform.translation = RESPAWN_POINT;
+	vitals.health = vitals.max_health;
+	active_effects.0.clear();
+
+	commands.entity(player).remove::<Fainted>();
+}
+
+fn show_vitals(
+	mut contexts: EguiContexts,
+	player_query: Query<(&Vitals, Option<&Fainted>), With<Player>>,
+	active_effects: Res<ActiveEffects>,
+) {
+	let Ok((vitals, fainted)) = player_query.get_single() else { return; };
+
+	egui::Area::new("vitals")
+		.anchor(egui::Align2::LEFT_TOP, egui::vec2(10.0, 10.0))
+		.show(contexts.ctx_mut(), |ui| {
+			ui.set_width(200.0);
+
+			let mut bar = egui::ProgressBar::new(vitals.health / vitals.max_health)
+				.text(format!("{:.0} / {:.0}", vitals.health, vitals.max_health));
+			if active_effects.has_effect(EffectType::GOD_MODE).is_some() {
+				bar = bar.fill(egui::Color32::GOLD);
+			}
+			ui.add(bar);
+
+			if fainted.is_some() {
+				ui.label("You fainted...");
+			}
+		});
+}
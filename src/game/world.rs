@@ -1,8 +1,14 @@
-use bevy::{math::Vec3Swizzles, scene::SceneInstance};
+use bevy::{asset::HandleId, math::Vec3Swizzles, scene::SceneInstance, utils::HashMap};
+use bevy_inspector_egui::bevy_egui::{egui, EguiContexts};
+use rand::rngs::StdRng;
 
 use crate::{
-	assets::{SceneInstanceReady, Spawnable, SpawnableArchetype, SHADOW_BUNDLE},
-	game::ingredient::{Ingredient, IngredientType},
+	assets::{SceneInstanceReady, Spawnable, SHADOW_BUNDLE},
+	game::{
+		biome::{generate_biome_map, spawn_biome_ground, Biome, BiomeMap},
+		effects::EffectRegistry,
+		ingredient::{Ingredient, IngredientSpecies, SpeciesRegistry},
+	},
 	prelude::*,
 };
 
@@ -10,11 +16,28 @@ pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
 	fn build(&self, app: &mut App) {
+		let seed = WorldSeed::from_args();
+		info!("World seed: {}", seed.0);
+
 		app.init_resource::<OccupiedSpawnSpace>()
+			.init_resource::<SpeciesRegistry>()
+			.init_resource::<BiomeMap>()
+			.insert_resource(WorldRng(StdRng::seed_from_u64(seed.0)))
+			.insert_resource(seed)
+			.add_startup_system(create_ball_assets)
 			.add_systems(
-				(init_world, spawn_spawnables).in_schedule(OnEnter(GameState::GeneratingWorld)),
+				(
+					init_world,
+					seed_world_rng,
+					generate_species.after(seed_world_rng),
+					generate_biome_map.after(generate_species),
+					spawn_biome_ground.after(generate_biome_map),
+					spawn_spawnables.after(generate_biome_map),
+				)
+					.in_schedule(OnEnter(GameState::GeneratingWorld)),
 			)
-			.add_system(check_if_finished.in_set(OnUpdate(GameState::GeneratingWorld)));
+			.add_system(check_if_finished.in_set(OnUpdate(GameState::GeneratingWorld)))
+			.add_system(show_seed.in_set(OnUpdate(GameState::InGame)));
 		// .add_systems((
 		// 	set_materials_to_spawnables,
 		// 		).in_set(OnUpdate(GameState::InGame))
@@ -22,14 +45,124 @@ impl Plugin for WorldPlugin {
 	}
 }
 
+/// Same seed always grows the same island
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldSeed(pub u64);
+
+impl WorldSeed {
+	/// Taken from `--seed <number>`, random if there is none
+	pub fn from_args() -> Self {
+		let mut args = std::env::args().skip_while(|a| a != "--seed").skip(1);
+
+		match args.next().map(|s| s.parse::<u64>()) {
+			Some(Ok(seed)) => WorldSeed(seed),
+			Some(Err(err)) => {
+				warn!("Invalid world seed: {err}, using a random one");
+				WorldSeed(thread_rng().gen())
+			}
+			None => WorldSeed(thread_rng().gen()),
+		}
+	}
+}
+
+/// Randomness for everything in `GameState::GeneratingWorld`, seeded from `WorldSeed`
+#[derive(Resource, Debug, Clone)]
+pub struct WorldRng(pub StdRng);
+
+/// Spots taken by spawnables, so new ones don't grow into them
+pub trait SpawnSpace {
+	fn insert(&mut self, position: Vec2, size: f32);
+
+	/// Two spots overlap, if they are closer than the square root of their sizes multiplied
+	fn is_occupied(&self, position: Vec2, size: f32) -> bool;
+
+	/// Frees the spot at exactly this position
+	fn remove(&mut self, position: Vec2);
+}
+
+/// Spots taken by spawnables, bucketed into a grid so only nearby ones have to be checked
 #[derive(Resource, Default, Debug)]
-pub struct OccupiedSpawnSpace(Vec<(Vec2, f32)>);
+pub struct OccupiedSpawnSpace {
+	cells: HashMap<IVec2, SmallVec<[(Vec2, f32); 4]>>,
+	/// Biggest size added so far, decides how many cells around have to be checked
+	max_size: f32,
+}
+
+impl OccupiedSpawnSpace {
+	const CELL_SIZE: f32 = 4.0;
+
+	fn cell(position: Vec2) -> IVec2 {
+		(position / Self::CELL_SIZE).floor().as_ivec2()
+	}
+}
+
+impl SpawnSpace for OccupiedSpawnSpace {
+	fn insert(&mut self, position: Vec2, size: f32) {
+		self.max_size = self.max_size.max(size);
+		self.cells
+			.entry(Self::cell(position))
+			.or_default()
+			.push((position, size));
+	}
+
+	fn is_occupied(&self, position: Vec2, size: f32) -> bool {
+		let reach = Vec2::splat((size * self.max_size).sqrt());
+		let min = Self::cell(position - reach);
+		let max = Self::cell(position + reach);
+
+		(min.x..=max.x)
+			.flat_map(|x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+			.filter_map(|cell| self.cells.get(&cell))
+			.flatten()
+			.any(|(occupied_pos, occupied_size)| {
+				let distance = Vec2::length_squared(position - *occupied_pos);
+
+				distance < (size * occupied_size)
+			})
+	}
+
+	fn remove(&mut self, position: Vec2) {
+		if let Some(cell) = self.cells.get_mut(&Self::cell(position)) {
+			cell.retain(|(occupied_pos, _)| occupied_pos.distance_squared(position) > 0.0001);
+		}
+	}
+}
+
+/// Checks every spot, like `OccupiedSpawnSpace` did before the grid. Kept to compare against.
+#[derive(Default, Debug)]
+pub struct LinearSpawnSpace(pub Vec<(Vec2, f32)>);
+
+impl SpawnSpace for LinearSpawnSpace {
+	fn insert(&mut self, position: Vec2, size: f32) {
+		self.0.push((position, size));
+	}
+
+	fn is_occupied(&self, position: Vec2, size: f32) -> bool {
+		self.0.iter().any(|(occupied_pos, occupied_size)| {
+			let distance = Vec2::length_squared(position - *occupied_pos);
+
+			distance < (size * occupied_size)
+		})
+	}
+
+	fn remove(&mut self, position: Vec2) {
+		self.0
+			.retain(|(occupied_pos, _)| occupied_pos.distance_squared(position) > 0.0001);
+	}
+}
+
+/// Parent of everything growing on the island
+#[derive(Component, Clone, Copy)]
+pub struct VegetationCollection;
 
 /// Added to the entities that represent shadows
 #[derive(Component, Clone, Copy)]
 pub struct Shadow;
 
-const ISLAND_SIZE: f32 = 200.0;
+pub const ISLAND_SIZE: f32 = 200.0;
+
+/// How many times world generation tries to find a free spot for a spawnable
+pub const SPAWN_ATTEMPTS: usize = 8000;
 
 #[derive(Component, Clone, Debug)]
 pub struct SpawnableInstance {
@@ -39,10 +172,62 @@ pub struct SpawnableInstance {
 	//pub archetype: SpawnableArchetype,
 }
 
-fn init_world(
+/// Bouncy ball, just for fun
+#[derive(Component)]
+pub struct Ball;
+
+/// Shared by every ball
+#[derive(Resource)]
+pub struct BallAssets {
+	mesh: Handle<Mesh>,
+	material: Handle<StandardMaterial>,
+}
+
+fn create_ball_assets(
 	mut commands: Commands,
 	mut meshes: ResMut<Assets<Mesh>>,
 	mut standard_mat: ResMut<Assets<StandardMaterial>>,
+) {
+	commands.insert_resource(BallAssets {
+		mesh: meshes.add(Mesh::from(shape::UVSphere {
+			radius: 0.4,
+			..default()
+		})),
+		material: standard_mat.add(StandardMaterial {
+			base_color: Color::WHITE,
+			..default()
+		}),
+	});
+}
+
+pub fn spawn_ball(commands: &mut Commands, ball_assets: &BallAssets, translation: Vec3) {
+	commands.spawn((
+		Name::new("Ball"),
+		Ball,
+		PbrBundle {
+			mesh: ball_assets.mesh.clone(),
+			material: ball_assets.material.clone(),
+			transform: Transform::from_translation(translation),
+			..default()
+		},
+		RigidBody::Dynamic,
+		Velocity::default(),
+		Collider::ball(0.4),
+		ColliderMassProperties::Density(0.05),
+		Restitution::new(0.70),
+		GravityScale(1.3),
+		Friction::new(0.2),
+		Dominance::group(-10),
+		CollisionGroups::new(
+			Group::GROUP_1 | Group::GROUP_3,
+			Group::GROUP_1 | Group::GROUP_3,
+		),
+	));
+}
+
+fn init_world(
+	mut commands: Commands,
+	ball_assets: Res<BallAssets>,
 	game_assets: Res<GameAssets>,
 	audio: Res<Audio>,
 ) {
@@ -73,41 +258,13 @@ fn init_world(
 		},
 		NamedMaterials(smallvec![
 			NamedMaterial::new("Island", Color::rgb(0.3, 0.15, 0.0)),
-			NamedMaterial::new("Grass", Color::YELLOW_GREEN),
+			NamedMaterial::new("Grass", Biome::ISLAND_GRASS.ground_color()),
 		]),
 		RigidBody::Fixed,
 		CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_3),
 	));
 
-	// sphere
-	commands.spawn((
-		Name::new("Ball"),
-		PbrBundle {
-			mesh: meshes.add(Mesh::from(shape::UVSphere {
-				radius: 0.4,
-				..default()
-			})),
-			material: standard_mat.add(StandardMaterial {
-				base_color: Color::WHITE,
-				..default()
-			}),
-			transform: Transform::from_xyz(120.5, 5.0, 110.5), //.with_scale(Vec3::splat(3.0)),
-			..default()
-		},
-		RigidBody::Dynamic,
-		//Velocity::default(),
-		Collider::ball(0.4),
-		ColliderMassProperties::Density(0.05),
-		Restitution::new(0.70),
-		GravityScale(1.3),
-		Friction::new(0.2),
-		Dominance::group(-10),
-		//RenderLayers::layer(2),
-		CollisionGroups::new(
-			Group::GROUP_1 | Group::GROUP_3,
-			Group::GROUP_1 | Group::GROUP_3,
-		),
-	));
+	spawn_ball(&mut commands, &ball_assets, Vec3::new(120.5, 5.0, 110.5));
 
 	// Light
 	commands.spawn(DirectionalLightBundle {
@@ -134,6 +291,26 @@ fn init_world(
 	commands.insert_resource(ClearColor(Color::BLACK));
 }
 
+/// Seed might have changed since the app was built
+fn seed_world_rng(seed: Res<WorldSeed>, mut world_rng: ResMut<WorldRng>) {
+	world_rng.0 = StdRng::seed_from_u64(seed.0);
+}
+
+/// Assets are stored in a hash map, so they have to be sorted to always come in the same order
+fn sorted_spawnables(spawnable_assets: &Assets<Spawnable>) -> Vec<(HandleId, &Spawnable)> {
+	let mut spawnables = spawnable_assets.iter().collect::<Vec<_>>();
+	spawnables.sort_by_key(|(_, s)| (s.archetype as usize, s.id));
+	spawnables
+}
+
+fn show_seed(mut contexts: EguiContexts, seed: Res<WorldSeed>) {
+	egui::Area::new("seed")
+		.anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(10.0, -10.0))
+		.show(contexts.ctx_mut(), |ui| {
+			ui.weak(format!("Seed: {}", seed.0));
+		});
+}
+
 fn check_if_finished(
 	unloaded_scenes: Query<(), (With<SceneInstance>, Without<SceneInstanceReady>)>,
 	mut next_state: ResMut<NextState<GameState>>,
@@ -144,145 +321,387 @@ fn check_if_finished(
 	}
 }
 
-/// Set spawn spawnable objects
-fn spawn_spawnables(
-	mut commands: Commands,
-	mut occupied_space: ResMut<OccupiedSpawnSpace>,
+/// Generates ingredient species for every spawnable with an ingredient
+fn generate_species(
+	mut species_registry: ResMut<SpeciesRegistry>,
 	spawnable_assets: Res<Assets<Spawnable>>,
+	effect_registry: Res<EffectRegistry>,
+	mut world_rng: ResMut<WorldRng>,
 ) {
-	let mut rng = rand::thread_rng();
+	let spawnables = sorted_spawnables(&spawnable_assets);
+	let spawnables = spawnables.iter().map(|(_, s)| *s).collect::<Vec<_>>();
 
-	let spawnables = spawnable_assets.iter().collect::<Vec<_>>();
-	let weights = spawnables
-		.iter()
-		.map(|s| s.1.spawn_weight)
-		.collect::<Vec<_>>();
+	*species_registry = SpeciesRegistry::generate(&spawnables, &effect_registry, &mut world_rng.0);
+}
 
-	let choose_spawnable = Choices {
-		choices: &spawnables,
-		weights: Some(&weights),
-	};
+/// Everything random about a spawnable growing in the world, decided before it's spawned
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnPlan {
+	pub position: Vec2,
+	/// Around the Y axis
+	pub rotation: f32,
+	pub is_rare: bool,
+	pub relative_scale: f32,
+	/// Index in `SpeciesRegistry`, if it grows an ingredient
+	pub species: Option<usize>,
+	pub materials: NamedMaterials,
+	/// Main color, the ingredient gets it too
+	pub color: Color,
+}
 
-	let collection = commands
-		.spawn((
-			Name::new("Vegetation collection"),
-			TransformBundle::default(),
-			VisibilityBundle::default(),
-		))
-		.id();
+impl SpawnPlan {
+	pub fn new(
+		spawnable: &Spawnable,
+		position: Vec2,
+		is_rare: bool,
+		relative_scale: f32,
+		species: Option<(usize, &IngredientSpecies)>,
+		rng: &mut impl Rng,
+	) -> Self {
+		let (materials, color) = NamedMaterials::generate_materials(
+			spawnable.archetype,
+			is_rare,
+			species.map(|(_, s)| s.hue.clone()),
+			rng,
+		);
+
+		SpawnPlan {
+			position,
+			rotation: rng.gen_range(-PI..PI),
+			is_rare,
+			relative_scale,
+			species: species.map(|(id, _)| id),
+			materials,
+			color,
+		}
+	}
+}
 
-	for _ in 0..8000 {
-		let Some((spawnable_handle,spawnable)) = choose_spawnable.get_random(&mut rng) else {
-			warn!("Couldn't randomly choose spawnable from assets!");
-			continue;
-		};
+/// Decides what grows where on the island.
+/// Returns the index of the spawnable in `spawnables`, with the plan for it.
+pub fn plan_spawnables(
+	spawnables: &[&Spawnable],
+	species_registry: &SpeciesRegistry,
+	biome_map: &BiomeMap,
+	occupied_space: &mut impl SpawnSpace,
+	attempts: usize,
+	rng: &mut StdRng,
+) -> Vec<(usize, SpawnPlan)> {
+	let indices = (0..spawnables.len()).collect::<Vec<_>>();
+
+	// Every biome has its own spawn table
+	let biome_weights = Biome::ALL.map(|biome| {
+		spawnables
+			.iter()
+			.map(|s| biome.spawn_weight(s))
+			.collect::<Vec<_>>()
+	});
 
+	let mut plans = vec![];
+
+	for _ in 0..attempts {
 		let position = (Quat::from_rotation_y(rng.gen_range(-PI..PI))
 			* (Vec3::Z * rng.gen_range(0.0..1.0f32).sqrt() * (ISLAND_SIZE - 5.0)))
 			.xz();
 
 		//let position = Vec2::new(rng.gen_range(-SPAWN_SIZE..SPAWN_SIZE), rng.gen_range(-SPAWN_SIZE..SPAWN_SIZE));
 
-		let is_rare = rng.gen_bool(1.0 / 200.0);
+		let biome = biome_map.biome_at(position);
+		if !rng.gen_bool(biome.density() as f64) {
+			continue;
+		}
+
+		let choose_spawnable = Choices {
+			choices: &indices,
+			weights: Some(&biome_weights[biome as usize]),
+		};
+
+		let Some(&index) = choose_spawnable.get_random(rng) else {
+			warn!("Couldn't randomly choose spawnable from assets!");
+			continue;
+		};
+		let spawnable = spawnables[index];
+
+		let is_rare = rng.gen_bool((biome.rare_chance() / 200.0) as f64);
+
+		let species = spawnable.ingredient.as_ref().and_then(|_| {
+			species_registry.random_for_spawnable((spawnable.archetype, spawnable.id), rng)
+		});
 
 		let relative_scale = if is_rare {
 			rng.gen_range(1.35..1.8)
 		} else {
 			rng.gen_range(0.7..1.35)
-		};
+		} * species.map(|(_, s)| s.base_size).unwrap_or(1.0);
 
 		if position.length_squared() < 12.0
-			|| is_occupied(position, spawnable.size * relative_scale, &occupied_space)
+			|| occupied_space.is_occupied(position, spawnable.size * relative_scale)
 		{
 			continue;
 		}
 
-		let mut handle = Handle::<Spawnable>::weak(*spawnable_handle);
-		handle.make_strong(&spawnable_assets);
-
 		// Set space as occupied
-		occupied_space
-			.0
-			.push((position, spawnable.size * relative_scale));
-
-		let (materials, color) =
-			NamedMaterials::generate_materials(spawnable.archetype, is_rare, &mut rng);
-
-		let mut entity = commands.spawn((
-			RigidBody::Fixed,
-			SpawnableInstance {
-				handle,
-				rare: is_rare,
-				size: relative_scale,
-				//archetype: spawnable.archetype,
-			},
-			SceneBundle {
-				scene: spawnable.scene.clone(),
-				transform: Transform::from_translation(Vec3::new(position.x, 0.0, position.y))
-					.with_scale(Vec3::splat(relative_scale))
-					.with_rotation(Quat::from_rotation_y(rng.gen_range(-PI..PI))),
-				..default()
-			},
-			CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_3),
-			// Applies materials to the spawned scene
-			materials,
+		occupied_space.insert(position, spawnable.size * relative_scale);
+
+		plans.push((
+			index,
+			SpawnPlan::new(spawnable, position, is_rare, relative_scale, species, rng),
 		));
+	}
 
-		if spawnable.ingredient.is_some() {
-			match spawnable.archetype {
-				SpawnableArchetype::Tree => {
-					entity.insert(());
-				}
-				SpawnableArchetype::Bush => {
-					entity.insert((Ingredient::generate_random_ingredient(
-						&mut rng,
-						IngredientType::Berry,
-						is_rare,
-						color,
-						relative_scale,
-					),));
-				}
-				SpawnableArchetype::Mushroom => {
-					entity.insert((Ingredient::generate_random_ingredient(
-						&mut rng,
-						IngredientType::Mushroom,
-						is_rare,
-						color,
-						relative_scale,
-					),));
-				}
-			}
-		}
+	plans
+}
 
-		// Collider
-		if let Some(collider) = &spawnable.collider {
-			entity.insert(collider.clone());
-		}
+/// Set spawn spawnable objects
+fn spawn_spawnables(
+	mut commands: Commands,
+	mut occupied_space: ResMut<OccupiedSpawnSpace>,
+	spawnable_assets: Res<Assets<Spawnable>>,
+	species_registry: Res<SpeciesRegistry>,
+	biome_map: Res<BiomeMap>,
+	mut world_rng: ResMut<WorldRng>,
+) {
+	let spawnables = sorted_spawnables(&spawnable_assets);
+
+	let plans = plan_spawnables(
+		&spawnables.iter().map(|(_, s)| *s).collect::<Vec<_>>(),
+		&species_registry,
+		&biome_map,
+		occupied_space.as_mut(),
+		SPAWN_ATTEMPTS,
+		&mut world_rng.0,
+	);
 
-		// Add shadow to entity
-		entity.with_children(|commands| {
-			commands.spawn((
-				Name::new("Shadow"),
-				Shadow,
-				SHADOW_BUNDLE.get().unwrap().clone(),
-				Transform::from_xyz(0.0, 0.02, 0.0).with_scale(Vec3::splat(spawnable.size)),
-				GlobalTransform::default(),
-				VisibilityBundle::default(),
-			));
-		});
+	let collection = commands
+		.spawn((
+			Name::new("Vegetation collection"),
+			VegetationCollection,
+			TransformBundle::default(),
+			VisibilityBundle::default(),
+		))
+		.id();
 
-		let entity = entity.id();
+	for (index, plan) in plans {
+		let (handle_id, spawnable) = spawnables[index];
+
+		let mut handle = Handle::<Spawnable>::weak(handle_id);
+		handle.make_strong(&spawnable_assets);
+
+		let entity =
+			spawn_spawnable_instance(&mut commands, spawnable, handle, plan, &species_registry);
 		commands.entity(collection).add_child(entity);
 	}
+}
 
-	fn is_occupied(position: Vec2, size: f32, occupied_space: &OccupiedSpawnSpace) -> bool {
-		occupied_space
-			.0
-			.iter()
-			.any(|(occupied_pos, occupied_size)| {
-				let distance = Vec2::length_squared(position - *occupied_pos);
+/// Spawns a spawnable into the world, with an ingredient if it has a species
+pub fn spawn_spawnable_instance(
+	commands: &mut Commands,
+	spawnable: &Spawnable,
+	handle: Handle<Spawnable>,
+	plan: SpawnPlan,
+	species_registry: &SpeciesRegistry,
+) -> Entity {
+	let mut entity = commands.spawn((
+		RigidBody::Fixed,
+		SpawnableInstance {
+			handle,
+			rare: plan.is_rare,
+			size: plan.relative_scale,
+			//archetype: spawnable.archetype,
+		},
+		SceneBundle {
+			scene: spawnable.scene.clone(),
+			transform: Transform::from_xyz(plan.position.x, 0.0, plan.position.y)
+				.with_scale(Vec3::splat(plan.relative_scale))
+				.with_rotation(Quat::from_rotation_y(plan.rotation)),
+			..default()
+		},
+		CollisionGroups::new(Group::GROUP_1, Group::GROUP_1 | Group::GROUP_3),
+		// Applies materials to the spawned scene
+		plan.materials,
+	));
 
-				distance < (size * occupied_size)
+	let species = plan
+		.species
+		.and_then(|id| species_registry.get(id).map(|s| (id, s)));
+	if let Some((species_id, species)) = species {
+		entity.insert(Ingredient::from_species(
+			species_id,
+			species,
+			plan.is_rare,
+			plan.color,
+			plan.relative_scale,
+		));
+	}
+
+	// Collider
+	if let Some(collider) = &spawnable.collider {
+		entity.insert(collider.clone());
+	}
+
+	// Add shadow to entity
+	entity.with_children(|commands| {
+		commands.spawn((
+			Name::new("Shadow"),
+			Shadow,
+			SHADOW_BUNDLE.get().unwrap().clone(),
+			Transform::from_xyz(0.0, 0.02, 0.0).with_scale(Vec3::splat(spawnable.size)),
+			GlobalTransform::default(),
+			VisibilityBundle::default(),
+		));
+	});
+
+	entity.id()
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		assets::{SpawnableArchetype, SpawnableIngredient},
+		game::{
+			effects::EffectsPlugin, hallucinations::HallucinationsPlugin, items::ItemsPlugin,
+			player::PlayerPlugin, thief::ThiefPlugin, vitals::VitalsPlugin,
+		},
+	};
+
+	use super::*;
+
+	fn spawnables() -> Vec<Spawnable> {
+		use SpawnableArchetype::*;
+
+		[Tree, Tree, Bush, Bush, Mushroom, Mushroom, Root, Plant]
+			.into_iter()
+			.enumerate()
+			.map(|(id, archetype)| Spawnable {
+				id,
+				archetype,
+				scene: Handle::default(),
+				// One bush without berries
+				ingredient: (archetype != Tree && id != 3).then(SpawnableIngredient::default),
+				spawn_weight: 1.0,
+				size: 1.0 + id as f32 * 0.2,
+				collider: None,
 			})
+			.collect()
+	}
+
+	/// Effects, as the plugins register them
+	fn effect_registry() -> EffectRegistry {
+		let mut app = App::new();
+		app.add_state::<GameState>()
+			.add_plugin(EffectsPlugin)
+			.add_plugin(PlayerPlugin)
+			.add_plugin(ItemsPlugin)
+			.add_plugin(ThiefPlugin)
+			.add_plugin(HallucinationsPlugin)
+			.add_plugin(VitalsPlugin);
+
+		app.world.resource::<EffectRegistry>().clone()
+	}
+
+	/// Same steps as in `GameState::GeneratingWorld`, without spawning anything
+	fn generate_world(seed: u64) -> (SpeciesRegistry, BiomeMap, Vec<(usize, SpawnPlan)>) {
+		let spawnables = spawnables();
+		let spawnables = spawnables.iter().collect::<Vec<_>>();
+
+		let mut rng = StdRng::seed_from_u64(seed);
+
+		let species_registry = SpeciesRegistry::generate(&spawnables, &effect_registry(), &mut rng);
+		let biome_map = BiomeMap::new(&mut rng);
+		let plans = plan_spawnables(
+			&spawnables,
+			&species_registry,
+			&biome_map,
+			&mut OccupiedSpawnSpace::default(),
+			SPAWN_ATTEMPTS,
+			&mut rng,
+		);
+
+		(species_registry, biome_map, plans)
+	}
+
+	#[test]
+	fn same_seed_grows_same_world() {
+		let (species, biome_map, plans) = generate_world(1234);
+		assert!(!species.0.is_empty());
+		assert!(!plans.is_empty());
+
+		assert_eq!(generate_world(1234), (species, biome_map, plans));
+	}
+
+	#[test]
+	fn different_seed_grows_different_world() {
+		let (species, biome_map, plans) = generate_world(1234);
+		let (other_species, other_biome_map, other_plans) = generate_world(4321);
+
+		assert_ne!(species, other_species);
+		assert_ne!(biome_map, other_biome_map);
+		assert_ne!(plans, other_plans);
+	}
+
+	fn random_spot(rng: &mut StdRng) -> (Vec2, f32) {
+		let position = Vec2::new(
+			rng.gen_range(-ISLAND_SIZE..ISLAND_SIZE),
+			rng.gen_range(-ISLAND_SIZE..ISLAND_SIZE),
+		);
+
+		(position, rng.gen_range(0.2..6.0))
+	}
+
+	#[test]
+	fn occupied_space_matches_linear_scan() {
+		let mut rng = StdRng::seed_from_u64(22);
+		let mut occupied_space = OccupiedSpawnSpace::default();
+		let mut linear_space = LinearSpawnSpace::default();
+
+		for i in 0..8000 {
+			let (position, size) = random_spot(&mut rng);
+			let expected = linear_space.is_occupied(position, size);
+
+			assert_eq!(
+				occupied_space.is_occupied(position, size),
+				expected,
+				"{position} {size}"
+			);
+
+			if !expected {
+				occupied_space.insert(position, size);
+				linear_space.insert(position, size);
+			}
+
+			// Harvested now and then
+			if i % 10 == 0 && !linear_space.0.is_empty() {
+				let (position, _) = linear_space.0[rng.gen_range(0..linear_space.0.len())];
+				linear_space.remove(position);
+				occupied_space.remove(position);
+			}
+		}
+	}
+
+	#[test]
+	fn grid_plans_like_linear_scan() {
+		let spawnables = spawnables();
+		let spawnables = spawnables.iter().collect::<Vec<_>>();
+		let species_registry = SpeciesRegistry::default();
+		let biome_map = BiomeMap::new(&mut StdRng::seed_from_u64(23));
+
+		let grid_plans = plan_spawnables(
+			&spawnables,
+			&species_registry,
+			&biome_map,
+			&mut OccupiedSpawnSpace::default(),
+			SPAWN_ATTEMPTS,
+			&mut StdRng::seed_from_u64(22),
+		);
+		let linear_plans = plan_spawnables(
+			&spawnables,
+			&species_registry,
+			&biome_map,
+			&mut LinearSpawnSpace::default(),
+			SPAWN_ATTEMPTS,
+			&mut StdRng::seed_from_u64(22),
+		);
+
+		assert_eq!(grid_plans, linear_plans);
 	}
 }
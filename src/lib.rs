@@ -0,0 +1,43 @@
+#![allow(clippy::type_complexity)]
+#![allow(clippy::too_many_arguments)]
+
+use crate::prelude::*;
+
+pub mod assets;
+pub mod game;
+mod helper;
+mod main_menu;
+
+#[cfg(debug_assertions)]
+pub mod debug;
+
+#[derive(States, Default, Debug, Clone, Eq, PartialEq, Hash, Reflect)]
+pub enum GameState {
+	#[default]
+	LoadingAssets,
+	MainMenu,
+	InGame,
+	GeneratingWorld,
+}
+
+pub mod prelude {
+	pub use bevy::prelude::*;
+	pub use bevy_rapier3d::prelude::*;
+	pub use leafwing_input_manager::prelude::*;
+	pub use rand::prelude::*;
+	pub use smallvec::*;
+	pub use std::f32::consts::PI;
+
+	pub use crate::assets::GameAssets;
+	pub use crate::game::input::*;
+	pub use crate::game::materials::*;
+	pub use crate::helper::*;
+	pub use crate::GameState;
+
+	#[cfg(debug_assertions)]
+	pub use bevy_inspector_egui::prelude::*;
+	#[cfg(debug_assertions)]
+	pub use bevy_inspector_egui::quick::WorldInspectorPlugin;
+	#[cfg(debug_assertions)]
+	pub use bevy_prototype_debug_lines::*;
+}